@@ -0,0 +1,74 @@
+//! Compares checking the conditions of an axiom with one solver using push and pop against a new solver per condition.
+//! Run with ```cargo bench --bench incremental```, an optional argument only runs the cases containing it.
+
+use std::time::{Duration, Instant};
+
+use partitionElection::ballots::get_profile;
+use partitionElection::proof::axiom::check_iteratively;
+use partitionElection::proof::rule::{Borda, Copeland, RuleConstructor, VotingRule};
+use partitionElection::proof::Proof;
+use z3::SatResult;
+
+/// The cases as (rule, ballot, axiom), all with three candidates.
+const CASES: &[(&str, &str, &str)] = &[
+    ("borda", "FP", "unam"),
+    ("borda", "FP", "maj"),
+    ("borda", "FP", "res"),
+    ("borda", "FP", "strat"),
+    ("copeland", "FP", "part"),
+    ("copeland", "PS", "maj"),
+    ("copeland", "PS", "cons"),
+    ("borda", "PA", "splitss"),
+    ("borda", "CS", "unam"),
+];
+
+/// Every case is checked this often in both modes, the fastest run is reported.
+const RUNS: usize = 3;
+
+fn run(rule: &str, ballot: &str, axiom: &str, incremental: bool) -> (SatResult, Duration) {
+    let borda = Borda::new(3);
+    let copeland = Copeland::new(3);
+    let rule: &dyn VotingRule = match rule {
+        "borda" => &borda,
+        _ => &copeland,
+    };
+    let ctx = z3::Context::new(&z3::Config::new());
+    let mut proof = Proof::new(3, rule, get_profile(3, ballot, &ctx));
+    proof.set_incremental(incremental);
+    let start = Instant::now();
+    let outcome = check_iteratively(axiom, &mut proof);
+    (outcome.result, start.elapsed())
+}
+
+fn fastest(rule: &str, ballot: &str, axiom: &str, incremental: bool) -> (SatResult, Duration) {
+    (0..RUNS)
+        .map(|_| run(rule, ballot, axiom, incremental))
+        .min_by_key(|(_, elapsed)| *elapsed)
+        .unwrap()
+}
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|x| !x.starts_with('-'));
+    println!(
+        "{:<10} {:<6} {:<8} {:<8} {:>12} {:>12}",
+        "rule", "ballot", "axiom", "result", "fresh", "push/pop"
+    );
+    for (rule, ballot, axiom) in CASES {
+        let name = format!("{} {} {}", rule, ballot, axiom);
+        if filter.as_ref().is_some_and(|x| !name.contains(x.as_str())) {
+            continue;
+        }
+        let (result, fresh) = fastest(rule, ballot, axiom, false);
+        let (incremental_result, incremental) = fastest(rule, ballot, axiom, true);
+        assert_eq!(result, incremental_result, "{}", name);
+        println!(
+            "{:<10} {:<6} {:<8} {:<8} {:>12.3?} {:>12.3?}",
+            rule,
+            ballot,
+            axiom,
+            format!("{:?}", result),
+            fresh,
+            incremental
+        );
+    }
+}
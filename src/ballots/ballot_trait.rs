@@ -13,11 +13,16 @@ pub trait Ballot {
     /// Returns the full name of the ballot
     fn get_full_name() -> String;
 
+    /// Returns the path of the file with the rankings for ```m``` candidates.
+    fn ranking_file(m: usize) -> String {
+        format!("logs/rankings/{}_{}.txt", m, Self::get_name())
+    }
+
     /// Returns all rankings possible with this ballot type.
     /// It reads them from a file. If the file does not exist, it will panic.
     /// The file can be generated with the `generate_ranking_file` method.
     fn all_rankings(m: usize) -> BTreeSet<Ranking> {
-        let path = format!("logs/rankings/{}_{}.txt", m, Self::get_name());
+        let path = Self::ranking_file(m);
 
         let lines = match read_from_file(&path) {
             Ok(lines) => lines,
@@ -57,6 +57,15 @@ macro_rules! create_profile {
             std::process::exit(1)
         }
 
+        /// Returns the path of the ranking file of a ballot given its name.
+        pub fn ranking_file(m: usize, ballot: &str) -> String {
+            $(if ballot.eq_ignore_ascii_case(&<$ballot>::get_name()) || ballot.eq_ignore_ascii_case(&<$ballot>::get_full_name()) {
+                return <$ballot>::ranking_file(m)
+            })*
+
+            eprintln!("Ballot {} not found", ballot);
+            std::process::exit(1)
+        }
 
         /// Creates a profile file for a given ballot.
         pub fn create_profile_file(m: usize, ballot: &str) -> () {
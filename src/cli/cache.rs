@@ -0,0 +1,45 @@
+use std::fs;
+
+use serde_json::{json, Value};
+
+const CACHE_DIR: &str = "logs/cache";
+
+/// Returns the 64 bit FNV-1a hash of ```bytes```.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Returns the hash of the ranking file, such that the cache is invalidated when the file changes.
+pub fn file_hash(path: &str) -> String {
+    match fs::read(path) {
+        Ok(content) => format!("{:016x}", fnv1a(&content)),
+        Err(_) => "missing".to_string(),
+    }
+}
+
+fn path(key: &str) -> String {
+    format!("{}/{:016x}.json", CACHE_DIR, fnv1a(key.as_bytes()))
+}
+
+/// Returns the cached output for ```key```, if any.
+/// The key is stored with the output, such that a hash collision is not mistaken for a hit.
+pub fn load(key: &str) -> Option<Value> {
+    let content = fs::read_to_string(path(key)).ok()?;
+    let mut entry = serde_json::from_str::<Value>(&content).ok()?;
+    if entry["key"] != key {
+        return None;
+    }
+    Some(entry["output"].take())
+}
+
+/// Stores the output for ```key```, a failure only loses the cache entry.
+pub fn store(key: &str, output: &Value) {
+    let entry = json!({ "key": key, "output": output });
+    let result = fs::create_dir_all(CACHE_DIR)
+        .and_then(|_| fs::write(path(key), serde_json::to_string(&entry).unwrap()));
+    if let Err(err) = result {
+        log::error!("Failed to write the cache {}: {}", path(key), err);
+    }
+}
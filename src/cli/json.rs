@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use z3::SatResult;
+
+use crate::proof::{AxiomOutcome, ConditionOutcome, ProofResult, QuantifiedOutcome};
+use crate::structures::{Partition, Ranking};
+
+/// Version of the JSON output, increase it whenever the layout changes.
+pub const VERSION: u32 = 2;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Prints the output of a command as versioned JSON.
+pub fn print(command: &str, output: Value) {
+    let mut document = json!({
+        "version": VERSION,
+        "command": command,
+    });
+    if let (Some(document), Value::Object(output)) = (document.as_object_mut(), output) {
+        document.extend(output);
+    }
+    println!("{}", serde_json::to_string_pretty(&document).unwrap());
+}
+
+pub fn sat_result(result: SatResult) -> Value {
+    match result {
+        SatResult::Sat => json!("sat"),
+        SatResult::Unsat => json!("unsat"),
+        SatResult::Unknown => json!("unknown"),
+    }
+}
+
+fn votes(profile: &BTreeMap<Ranking, u64>) -> Value {
+    profile
+        .iter()
+        .map(|(ranking, votes)| json!({ "ranking": ranking.to_string(), "votes": votes }))
+        .collect()
+}
+
+fn partitions(partitions: &BTreeSet<Partition>) -> Value {
+    partitions
+        .iter()
+        .map(|partition| partition.to_string())
+        .collect()
+}
+
+fn quantified_outcome(outcome: &QuantifiedOutcome) -> Value {
+    json!({
+        "label": outcome.label,
+        "profile": votes(&outcome.profile),
+        "winners": partitions(&outcome.winners),
+    })
+}
+
+fn condition_outcome(outcome: &ConditionOutcome) -> Value {
+    let profile = outcome.profile.as_ref().map(votes);
+    let winners = outcome.winners.as_ref().map(partitions);
+    let scores = outcome.scores.as_ref().map(|scores| {
+        scores
+            .iter()
+            .map(|(partition, score)| json!({ "partition": partition.to_string(), "score": score }))
+            .collect::<Vec<_>>()
+    });
+    json!({
+        "index": outcome.index,
+        "held": outcome.held,
+        "profile": profile,
+        "winners": winners,
+        "verified": outcome.verified,
+        "scores": scores,
+        "reason": outcome.reason,
+        "quantified": outcome.quantified.iter().map(quantified_outcome).collect::<Vec<_>>(),
+    })
+}
+
+pub fn axiom_outcome(outcome: &AxiomOutcome) -> Value {
+    json!({
+        "name": outcome.name,
+        "result": sat_result(outcome.result),
+        "conditions": outcome.conditions.iter().map(condition_outcome).collect::<Vec<_>>(),
+    })
+}
+
+pub fn proof_result(result: &ProofResult) -> Value {
+    json!({
+        "result": sat_result(result.result),
+        "axioms": result.axioms.iter().map(axiom_outcome).collect::<Vec<_>>(),
+        "unsat_core": result.unsat_core,
+        "reason": result.reason,
+    })
+}
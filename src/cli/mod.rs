@@ -1,9 +1,13 @@
 use clap::Parser;
 use clap::Subcommand;
+use serde_json::json;
 
 use crate::ballots::all_ballot_names;
 use crate::proof::axiom::all_axiom_names;
 use crate::proof::rule::all_rule_names;
+use crate::proof::Limits;
+pub mod cache;
+pub mod json;
 pub mod profile;
 pub mod proof;
 pub mod score;
@@ -27,7 +31,14 @@ enum Commands {
     Score(score::Args),
 
     /// Shows all Ballots, Rules and Axioms
-    Overview,
+    Overview(OverviewArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct OverviewArgs {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: json::Format,
 }
 
 pub fn run(args: Cli) {
@@ -35,15 +46,44 @@ pub fn run(args: Cli) {
         Commands::Profile(args) => profile::run(&args),
         Commands::Proof(args) => proof::run(&args),
         Commands::Score(args) => score::run(&args),
-        Commands::Overview => overview(),
+        Commands::Overview(args) => overview(&args),
+    }
+}
+
+/// Returns the solver limits given the timeout per check in milliseconds, the total timeout in seconds and the memory in megabytes.
+pub fn limits(timeout: Option<u32>, total_timeout: Option<u64>, memory: Option<u32>) -> Limits {
+    Limits {
+        condition_timeout: timeout,
+        total_timeout: total_timeout.map(|seconds| seconds * 1000),
+        memory,
     }
 }
 
-pub fn overview() {
+pub fn overview(args: &OverviewArgs) {
     let all_ballot_names = all_ballot_names();
     let all_rule_names = all_rule_names();
     let all_axiom_names = all_axiom_names();
 
+    if args.format == json::Format::Json {
+        let ballots = all_ballot_names
+            .iter()
+            .map(|(short, long)| json!({ "short": short, "full": long }))
+            .collect::<Vec<_>>();
+        let axioms = all_axiom_names
+            .iter()
+            .map(|(short, long)| json!({ "short": short, "full": long }))
+            .collect::<Vec<_>>();
+        json::print(
+            "overview",
+            json!({
+                "ballots": ballots,
+                "rules": all_rule_names,
+                "axioms": axioms,
+            }),
+        );
+        return;
+    }
+
     println!("There are {} Ballots:", all_ballot_names.len());
 
     for (short, long) in all_ballot_names {
@@ -1,12 +1,17 @@
 use clap::{Parser, Subcommand};
 
 use crate::ballots::create_profile_file;
+use crate::cli::json::Format;
 mod utils;
 
 #[derive(Subcommand, Debug)]
 pub enum ProfileCommands {
     /// List all profiles
-    List,
+    List {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
 
     /// Print a profile
     Print {
@@ -35,8 +40,8 @@ pub struct Args {
 
 pub fn run(args: &Args) {
     match &args.command {
-        ProfileCommands::List => {
-            utils::list();
+        ProfileCommands::List { format } => {
+            utils::list(*format);
         }
         ProfileCommands::Print { name } => {
             utils::list_profile(name);
@@ -1,8 +1,10 @@
 use prettytable::{format, row, Cell, Row, Table};
+use serde_json::json;
 use std::fs;
 use z3::{Config, Context};
 
 use crate::ballots::{name_to_full_name, *};
+use crate::cli::json::{self, Format};
 
 struct ProfileMetadata {
     candidate_size: u8,
@@ -10,7 +12,7 @@ struct ProfileMetadata {
     num_rankings: usize,
 }
 
-pub fn list() {
+pub fn list(output_format: Format) {
     let paths = fs::read_dir("logs/rankings");
 
     if paths.is_err() {
@@ -55,6 +57,22 @@ pub fn list() {
         other => other,
     });
 
+    if output_format == Format::Json {
+        let profiles = profiles
+            .iter()
+            .map(|profile| {
+                json!({
+                    "name": format!("{}_{}", profile.candidate_size, profile.ballot_name),
+                    "candidates": profile.candidate_size,
+                    "ballot": name_to_full_name(&profile.ballot_name),
+                    "rankings": profile.num_rankings,
+                })
+            })
+            .collect::<Vec<_>>();
+        json::print("profile list", json!({ "profiles": profiles }));
+        return;
+    }
+
     for profile in profiles {
         table.add_row(Row::new(vec![
             Cell::new(format!("{}_{}", profile.candidate_size, profile.ballot_name).as_str()),
@@ -1,17 +1,24 @@
-use std::{fs, io};
+use std::{fs, io, path::Path, time::Instant};
 
 use clap::Parser;
 use log::warn;
+use serde_json::json;
 use simplelog::{ConfigBuilder, LevelFilter, WriteLogger};
+use z3::{Context, SatResult};
 
-use crate::ballots::get_profile;
-use crate::proof::Proof;
+use crate::ballots::{get_profile, ranking_file};
+use crate::cli::cache;
+use crate::cli::json::{self, Format};
+use crate::cli::limits;
 use crate::proof::{
     axiom::{
-        add_axiom, all_axiom_names, check_iteratively, get_axiom_full_name, get_axiom_short_name,
+        add_axiom, all_axiom_names, axiom_evaluates_concretely, axiom_requires_candidate_voters,
+        brute_force, check_iteratively, check_iteratively_parallel, condition_smt2,
+        get_axiom_full_name, get_axiom_short_name, smallest_electorate,
     },
-    rule::{get_rule, get_rule_name},
+    rule::{get_rule, get_rule_name, TieBreaking, VotingRule},
 };
+use crate::proof::{checkpoint::Checkpoint, parallel::BuildProof, AxiomOutcome, Proof};
 
 #[derive(Parser, Debug)]
 pub struct Args {
@@ -42,6 +49,112 @@ pub struct Args {
     /// To file
     #[arg(short, long)]
     pub output: bool,
+
+    /// The voters are the candidates (one voter per candidate)
+    #[arg(long)]
+    pub candidate_voters: bool,
+
+    /// Break ties by a fixed priority order ("priority") or by a secondary rule and then the priority order
+    #[arg(short, long)]
+    pub tie_break: Option<String>,
+
+    /// Shrink conflicting axioms to a minimal unsatisfiable subset
+    #[arg(long)]
+    pub minimal_core: bool,
+
+    /// Exact number of voters
+    #[arg(long)]
+    pub voters: Option<u64>,
+
+    /// Maximal number of voters
+    #[arg(long)]
+    pub max_voters: Option<u64>,
+
+    /// Search the smallest number of voters violating the axiom, up to the maximal number of voters (requires -i)
+    #[arg(long, requires_all = ["iteratively", "max_voters"])]
+    pub smallest_electorate: bool,
+
+    /// Check the conditions with this many threads (requires -i)
+    #[arg(
+        short,
+        long,
+        requires = "iteratively",
+        conflicts_with = "smallest_electorate",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub jobs: Option<usize>,
+
+    /// Minimize the number of voters and rankings of counterexamples (requires -i)
+    #[arg(long, requires = "iteratively")]
+    pub minimize: bool,
+
+    /// Create a new solver for every condition instead of reusing one solver per axiom with push and pop (requires -i)
+    #[arg(long, requires = "iteratively")]
+    pub fresh_solver: bool,
+
+    /// Only search counterexamples which are the representative of their relabelings of the candidates, for neutral rules and forall axioms (requires -i)
+    #[arg(
+        long,
+        requires = "iteratively",
+        conflicts_with_all = ["brute_force", "emit_smt2"]
+    )]
+    pub symmetry: bool,
+
+    /// Skip conditions which relabel the candidates of an earlier condition, for neutral rules (requires -i)
+    #[arg(
+        long,
+        requires = "iteratively",
+        conflicts_with_all = ["symmetry", "brute_force", "emit_smt2"]
+    )]
+    pub deduplicate: bool,
+
+    /// Check the axiom on every profile up to the number of voters without the solver (requires -i and --voters or --max-voters)
+    #[arg(
+        long,
+        requires = "iteratively",
+        conflicts_with_all = ["jobs", "smallest_electorate", "emit_smt2"]
+    )]
+    pub brute_force: bool,
+
+    /// Record the conditions which held in a checkpoint, such that an interrupted run can be resumed (requires -i)
+    #[arg(
+        long,
+        requires = "iteratively",
+        conflicts_with_all = ["jobs", "smallest_electorate", "brute_force"]
+    )]
+    pub checkpoint: bool,
+
+    /// Skip the conditions which held in a previous interrupted run and keep recording them (requires -i)
+    #[arg(
+        long,
+        requires = "iteratively",
+        conflicts_with_all = ["jobs", "smallest_electorate", "brute_force"]
+    )]
+    pub resume: bool,
+
+    /// Write the formula, or every condition with -i, as SMT-LIB2 files to this directory instead of checking it
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["jobs", "smallest_electorate", "resume"])]
+    pub emit_smt2: Option<String>,
+
+    /// Check again instead of using a cached result, the new result replaces the cached one
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Timeout of a single check in milliseconds
+    #[arg(long)]
+    pub timeout: Option<u32>,
+
+    /// Timeout of all checks together in seconds
+    #[arg(long)]
+    pub total_timeout: Option<u64>,
+
+    /// Maximal memory used by Z3 in megabytes
+    #[arg(long)]
+    pub memory: Option<u32>,
+
+    /// Output format, in JSON mode the log is written to stderr
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
 }
 
 fn setup_logging(args: &Args) {
@@ -78,40 +191,176 @@ fn setup_logging(args: &Args) {
         fs::create_dir_all(path.clone()).unwrap();
         let file = std::fs::File::create(format!("{}/{}", path, filename)).unwrap();
         let _ = WriteLogger::init(level, config, file);
+    } else if args.format == Format::Json {
+        let _ = WriteLogger::init(level, config, io::stderr());
     } else {
         let _ = WriteLogger::init(level, config, io::stdout());
     };
 }
 
+/// Opens the checkpoint of the iterative proof of ```axiom```.
+/// The settings affecting the conditions are stored with the checkpoint, a checkpoint with other settings is not resumed.
+fn open_checkpoint(args: &Args, axiom: &str) -> Checkpoint {
+    let path = format!(
+        "logs/checkpoints/{}/{}{}_{}.txt",
+        get_rule_name(&args.rule),
+        args.candidates,
+        args.ballot,
+        get_axiom_short_name(axiom)
+    );
+    let settings = format!(
+        "candidates={} rule={} ballot={} axiom={} tie_break={:?} candidate_voters={} voters={:?} max_voters={:?} symmetry={} deduplicate={} ballots={} encoding={}",
+        args.candidates,
+        get_rule_name(&args.rule),
+        args.ballot,
+        get_axiom_short_name(axiom),
+        args.tie_break,
+        args.candidate_voters,
+        args.voters,
+        args.max_voters,
+        args.symmetry,
+        args.deduplicate,
+        cache::file_hash(&ranking_file(args.candidates as usize, &args.ballot)),
+        ENCODING_VERSION
+    );
+    Checkpoint::open(Path::new(&path), &settings, args.resume).unwrap_or_else(|err| {
+        eprintln!("Error: {:#?}", err);
+        eprintln!("Failed to open the checkpoint {}", path);
+        std::process::exit(1)
+    })
+}
+
+/// Builds the proof described by the arguments, the rules are leaked to live as long as the context.
+fn build_proof<'ctx>(args: &Args, ctx: &'ctx Context) -> Proof<'ctx> {
+    let rule = get_rule(args.candidates as usize, &args.rule);
+    let rule = Box::leak(rule);
+    let rule = rule as &dyn VotingRule;
+    let rule = match &args.tie_break {
+        None => rule,
+        Some(tie_break) => {
+            let secondary = if tie_break.eq_ignore_ascii_case("priority") {
+                None
+            } else {
+                let secondary = Box::leak(get_rule(args.candidates as usize, tie_break));
+                Some(secondary as &dyn VotingRule)
+            };
+            let tie_breaking = TieBreaking::create(args.candidates as usize, rule, secondary);
+            Box::leak(Box::new(tie_breaking)) as &dyn VotingRule
+        }
+    };
+    let mut profile = get_profile(args.candidates as usize, &args.ballot, ctx);
+    if args.candidate_voters {
+        profile = profile.with_candidate_voters();
+    }
+    profile.voters = args.voters;
+    profile.max_voters = args.max_voters;
+    let mut proof = Proof::new(args.candidates as usize, rule, profile);
+    proof.set_minimize(args.minimize);
+    proof.set_incremental(!args.fresh_solver);
+    proof.set_symmetry(args.symmetry);
+    proof.set_deduplicate(args.deduplicate);
+    proof.set_limits(limits(args.timeout, args.total_timeout, args.memory));
+    proof
+}
+
 pub fn run(args: &Args) {
+    let start = Instant::now();
     let mut axioms: Vec<String> = args.axiom.clone();
     if axioms.is_empty() {
         axioms = all_axiom_names()
             .into_iter()
             .map(|(short, _)| short.to_string())
+            .filter(|short| args.candidate_voters || !axiom_requires_candidate_voters(short))
             .collect::<Vec<String>>();
         eprintln!("No axiom provided. We will take all axioms");
     }
 
+    if let Some(axiom) = axioms
+        .iter()
+        .find(|axiom| !args.candidate_voters && axiom_requires_candidate_voters(axiom))
+    {
+        eprintln!(
+            "Axiom {} requires --candidate-voters",
+            get_axiom_full_name(axiom)
+        );
+        std::process::exit(1);
+    }
+
+    if let Some(axiom) = axioms
+        .iter()
+        .find(|axiom| args.brute_force && !axiom_evaluates_concretely(axiom))
+    {
+        eprintln!(
+            "Axiom {} can not be evaluated without the solver, --brute-force is not supported",
+            get_axiom_full_name(axiom)
+        );
+        std::process::exit(1);
+    }
+
     if axioms.len() > 1 && args.iteratively {
         eprintln!("Iteratively checking multiple axioms is not supported");
         std::process::exit(1);
     }
 
-    let ctx = z3::Context::new(&z3::Config::new());
-    let rule = get_rule(args.candidates as usize, &args.rule);
-    let rule = Box::leak(rule);
-    let rule = rule as &dyn crate::proof::rule::VotingRule;
-    let profile = get_profile(args.candidates as usize, &args.ballot, &ctx);
-    let mut proof = Proof::new(args.candidates as usize, rule, profile);
     setup_logging(args);
 
+    if let Some(dir) = &args.emit_smt2 {
+        emit_smt2(args, &axioms, dir);
+        return;
+    }
+
+    let key = cache_key(args, &axioms);
+    if !args.no_cache {
+        if let Some(output) = cache::load(&key) {
+            print_cached(args, &axioms, output, start);
+            return;
+        }
+    }
+
     log::info!("Starting proof");
 
+    if let Some(jobs) = args.jobs {
+        // Every worker builds its own proof.
+        let axiom_name = args.axiom.first().unwrap();
+        let check_start = Instant::now();
+        let build: &BuildProof = &|ctx| build_proof(args, ctx);
+        let outcome = match check_iteratively_parallel(axiom_name, jobs, build) {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                eprintln!("Failed to check in parallel: {}", error);
+                std::process::exit(1);
+            }
+        };
+        log::warn!("FINAL RESULT: {:?}", outcome.result);
+        finish_iteratively(args, &axioms, &key, &outcome, None, start, check_start);
+        return;
+    }
+
+    let ctx = z3::Context::new(&z3::Config::new());
+    let mut proof = build_proof(args, &ctx);
+
     if args.iteratively {
         let axiom_name = args.axiom.first().unwrap();
-        let result = check_iteratively(axiom_name, &mut proof);
-        log::warn!("FINAL RESULT: {:?}", result);
+        let check_start = Instant::now();
+        let (outcome, smallest) = if args.brute_force {
+            if args.voters.is_none() && args.max_voters.is_none() {
+                eprintln!("Enumerating profiles requires --voters or --max-voters");
+                std::process::exit(1);
+            }
+            (brute_force(axiom_name, &mut proof), None)
+        } else if args.smallest_electorate {
+            smallest_electorate(axiom_name, &mut proof)
+        } else {
+            if args.checkpoint || args.resume {
+                proof.set_checkpoint(Some(open_checkpoint(args, axiom_name)));
+            }
+            (check_iteratively(axiom_name, &mut proof), None)
+        };
+        log::warn!("FINAL RESULT: {:?}", outcome.result);
+        if let Some(voters) = smallest {
+            log::warn!("Smallest electorate: {} voters", voters);
+        }
+        finish_iteratively(args, &axioms, &key, &outcome, smallest, start, check_start);
         return;
     }
 
@@ -120,10 +369,239 @@ pub fn run(args: &Args) {
         add_axiom(axiom, &mut proof);
     }
 
-    let (result, _) = proof.check();
+    let check_start = Instant::now();
+    let result = proof.check();
+    log::warn!("FINAL RESULT: {:?}", result.result);
+
+    let mut output = json::proof_result(&result);
+    output["minimal_core"] = json!(null);
+    if result.result == SatResult::Unsat && args.minimal_core {
+        let subset = proof.minimal_unsat_subset(&result.unsat_core);
+        log_unsat_subset(&subset.axioms, subset.minimal);
+        output["minimal_core"] = json!(subset.axioms);
+        output["minimal_core_proven"] = json!(subset.minimal);
+    }
+    finish(args, &axioms, &key, output, start, check_start);
+}
+
+/// Logs the unsatisfiable subset of the axioms, noting if it is not proven minimal.
+fn log_unsat_subset(axioms: &[&str], minimal: bool) {
+    if minimal {
+        log::warn!("Minimal unsatisfiable subset: {}", axioms.join(", "));
+    } else {
+        log::warn!(
+            "Unsatisfiable subset, not proven minimal as some checks were unknown: {}",
+            axioms.join(", ")
+        );
+    }
+}
+
+/// Prints and caches the outcome of an iterative check.
+fn finish_iteratively(
+    args: &Args,
+    axioms: &[String],
+    key: &str,
+    outcome: &AxiomOutcome,
+    smallest: Option<u64>,
+    start: Instant,
+    check_start: Instant,
+) {
+    let output = json!({
+        "result": json::sat_result(outcome.result),
+        "axioms": [json::axiom_outcome(outcome)],
+        "unsat_core": [],
+        "smallest_electorate": smallest,
+        "minimal_core": null,
+    });
+    finish(args, axioms, key, output, start, check_start);
+}
+
+/// Version of the encoding of the axioms and rules, bump it whenever a change alters the cached results.
+const ENCODING_VERSION: u32 = 2;
+
+/// Returns the cache key of the proof, it contains every argument affecting the result, the hash of the ranking file and the versions of the encoding.
+/// Limits and the number of jobs are omitted, they only decide whether a result is found.
+fn cache_key(args: &Args, axioms: &[String]) -> String {
+    let axioms = axioms
+        .iter()
+        .map(|axiom| get_axiom_short_name(axiom))
+        .collect::<Vec<_>>();
+    format!(
+        "candidates={} rule={} ballot={} axioms={} iteratively={} tie_break={:?} candidate_voters={} voters={:?} max_voters={:?} smallest_electorate={} brute_force={} symmetry={} deduplicate={} minimize={} minimal_core={} rankings={} version={} encoding={}",
+        args.candidates,
+        get_rule_name(&args.rule),
+        args.ballot.to_ascii_uppercase(),
+        axioms.join(","),
+        args.iteratively,
+        args.tie_break.as_ref().map(|x| x.to_ascii_lowercase()),
+        args.candidate_voters,
+        args.voters,
+        args.max_voters,
+        args.smallest_electorate,
+        args.brute_force,
+        args.symmetry,
+        args.deduplicate,
+        args.minimize,
+        args.minimal_core,
+        cache::file_hash(&ranking_file(args.candidates as usize, &args.ballot)),
+        env!("CARGO_PKG_VERSION"),
+        ENCODING_VERSION
+    )
+}
+
+/// Stores a decided result in the cache and prints it in JSON mode.
+fn finish(
+    args: &Args,
+    axioms: &[String],
+    key: &str,
+    mut output: serde_json::Value,
+    start: Instant,
+    check_start: Instant,
+) {
+    if output["result"] != json::sat_result(SatResult::Unknown) {
+        cache::store(key, &output);
+    }
+    if args.format == Format::Json {
+        output["cached"] = json!(false);
+        print_json(args, axioms, output, start, check_start);
+    }
+}
+
+/// Reports a result found in the cache.
+fn print_cached(args: &Args, axioms: &[String], mut output: serde_json::Value, start: Instant) {
+    if args.format == Format::Json {
+        output["cached"] = json!(true);
+        print_json(args, axioms, output, start, start);
+        return;
+    }
+    log::warn!("Cached result, use --no-cache to check again");
+    for axiom in output["axioms"].as_array().into_iter().flatten() {
+        let failed = axiom["conditions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|condition| condition["held"] == false);
+        if let Some(profile) = failed.and_then(|x| x["profile"].as_array()) {
+            log::warn!(
+                "Counterexample for {}:",
+                axiom["name"].as_str().unwrap_or_default()
+            );
+            for entry in profile {
+                log::warn!(
+                    "{} -> {}",
+                    entry["votes"],
+                    entry["ranking"].as_str().unwrap_or_default()
+                );
+            }
+        }
+    }
+    if let Some(core) = output["unsat_core"].as_array().filter(|x| !x.is_empty()) {
+        let core = core.iter().filter_map(|x| x.as_str()).collect::<Vec<_>>();
+        log::warn!("Unsat core: {}", core.join(", "));
+    }
+    if let Some(voters) = output["smallest_electorate"].as_u64() {
+        log::warn!("Smallest electorate: {} voters", voters);
+    }
+    if let Some(core) = output["minimal_core"].as_array() {
+        let core = core.iter().filter_map(|x| x.as_str()).collect::<Vec<_>>();
+        log_unsat_subset(&core, output["minimal_core_proven"].as_bool() == Some(true));
+    }
+    let result = match output["result"].as_str() {
+        Some("sat") => SatResult::Sat,
+        Some("unsat") => SatResult::Unsat,
+        _ => SatResult::Unknown,
+    };
     log::warn!("FINAL RESULT: {:?}", result);
 }
 
+/// Writes the formula of the axioms, or every condition if checking iteratively, as SMT-LIB2 files to ```dir```.
+fn emit_smt2(args: &Args, axioms: &[String], dir: &str) {
+    let ctx = z3::Context::new(&z3::Config::new());
+    let mut proof = build_proof(args, &ctx);
+    let prefix = format!(
+        "{}{}_{}_{}",
+        args.candidates,
+        args.ballot,
+        get_rule_name(&args.rule),
+        axioms
+            .iter()
+            .map(|axiom| get_axiom_short_name(axiom))
+            .collect::<Vec<_>>()
+            .join("-")
+    );
+
+    let problems = if args.iteratively {
+        condition_smt2(&axioms[0], &mut proof)
+            .into_iter()
+            .enumerate()
+            .map(|(index, problem)| (format!("{}_{}.smt2", prefix, index), problem))
+            .collect::<Vec<_>>()
+    } else {
+        for axiom in axioms.iter() {
+            add_axiom(axiom, &mut proof);
+        }
+        vec![(format!("{}.smt2", prefix), proof.smt2())]
+    };
+
+    fs::create_dir_all(dir).unwrap_or_else(|err| {
+        eprintln!("Error: {:#?}", err);
+        eprintln!("Failed to create the directory {}", dir);
+        std::process::exit(1)
+    });
+    for (filename, problem) in problems.iter() {
+        let path = Path::new(dir).join(filename);
+        fs::write(&path, problem).unwrap_or_else(|err| {
+            eprintln!("Error: {:#?}", err);
+            eprintln!("Failed to write {}", path.display());
+            std::process::exit(1)
+        });
+    }
+    log::warn!("Wrote {} SMT-LIB2 files to {}", problems.len(), dir);
+}
+
+/// Prints the result together with the inputs and the timings.
+fn print_json(
+    args: &Args,
+    axioms: &[String],
+    mut output: serde_json::Value,
+    start: Instant,
+    check_start: Instant,
+) {
+    let axioms = axioms
+        .iter()
+        .map(|axiom| get_axiom_short_name(axiom))
+        .collect::<Vec<_>>();
+    output["inputs"] = json!({
+        "m": args.candidates,
+        "rule": get_rule_name(&args.rule),
+        "ballot": args.ballot,
+        "axioms": axioms,
+        "iteratively": args.iteratively,
+        "candidate_voters": args.candidate_voters,
+        "tie_break": args.tie_break,
+        "voters": args.voters,
+        "max_voters": args.max_voters,
+        "minimize": args.minimize,
+        "fresh_solver": args.fresh_solver,
+        "symmetry": args.symmetry,
+        "deduplicate": args.deduplicate,
+        "timeout": args.timeout,
+        "total_timeout": args.total_timeout,
+        "memory": args.memory,
+        "jobs": args.jobs,
+        "checkpoint": args.checkpoint,
+        "resume": args.resume,
+        "brute_force": args.brute_force,
+        "no_cache": args.no_cache,
+    });
+    output["timings"] = json!({
+        "setup_ms": (check_start - start).as_millis(),
+        "check_ms": check_start.elapsed().as_millis(),
+        "total_ms": start.elapsed().as_millis(),
+    });
+    json::print("proof", output);
+}
+
 // pub fn create_proof<'a : 'b, 'b>(
 //     candidates: u8,
 //     rule: &'b str,
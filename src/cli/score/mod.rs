@@ -1,11 +1,26 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, time::Instant};
 
 use clap::Parser;
+use itertools::Itertools;
+use prettytable::{format, row, Table};
+use serde_json::{json, Value};
 use z3::{Model, SatResult};
 
 use crate::{
     ballots::get_profile,
-    proof::{axiom::add_axiom, rule::Scoring, Proof},
+    cli::{
+        json::{self, Format},
+        limits,
+    },
+    proof::{
+        axiom::{
+            add_axiom, add_negated_axiom, all_axiom_names, axiom_requires_candidate_voters,
+            get_axiom_full_name, get_axiom_short_name,
+        },
+        rule::Scoring,
+        sweep::{sweep, Sweep},
+        Proof,
+    },
 };
 
 #[derive(Parser, Debug)]
@@ -21,30 +36,118 @@ pub struct Args {
     /// Axiom to check
     #[arg(short, long, num_args = 0..)]
     pub axiom: Vec<String>,
+
+    /// Shrink conflicting axioms to a minimal unsatisfiable subset
+    #[arg(long)]
+    pub minimal_core: bool,
+
+    /// Check every combination of the axioms (all axioms if none are given)
+    #[arg(long)]
+    pub sweep: bool,
+
+    /// Check if the axioms imply this axiom for every scoring system
+    #[arg(long, conflicts_with = "sweep")]
+    pub implies: Option<String>,
+
+    /// Exact number of voters
+    #[arg(long)]
+    pub voters: Option<u64>,
+
+    /// Maximal number of voters
+    #[arg(long)]
+    pub max_voters: Option<u64>,
+
+    /// Timeout of a single check in milliseconds
+    #[arg(long)]
+    pub timeout: Option<u32>,
+
+    /// Timeout of all checks together in seconds
+    #[arg(long)]
+    pub total_timeout: Option<u64>,
+
+    /// Maximal memory used by Z3 in megabytes
+    #[arg(long)]
+    pub memory: Option<u32>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
 }
 
-fn print_scoring_system(order_types: BTreeSet<Vec<usize>>, model: Model, rule: &Scoring) {
-    for order_type in order_types {
-        let score = rule.get_score_vector(&order_type);
-        let values = score
-            .iter()
-            .map(|x| {
-                let s = model.get_const_interp(x).unwrap();
-                let (a, b) = s.as_real().unwrap();
-                format!("{}/{}", a, b)
-            })
-            .collect::<Vec<_>>();
+fn print_sweep(sweep: &Sweep) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.set_titles(row!["Family", "Axioms"]);
+
+    for axioms in &sweep.maximal_satisfiable {
+        table.add_row(row!["Maximal satisfiable", axioms.iter().join(", ")]);
+    }
+    for axioms in &sweep.minimal_unsatisfiable {
+        table.add_row(row!["Minimal unsatisfiable", axioms.iter().join(", ")]);
+    }
+    for axioms in &sweep.unknown {
+        table.add_row(row!["Unknown", axioms.iter().join(", ")]);
+    }
+    table.printstd();
+
+    println!("Checked {} combinations", sweep.checked);
+}
+
+/// Returns the score vector of every order type given the model.
+fn scoring_system(
+    order_types: &BTreeSet<Vec<usize>>,
+    model: &Model,
+    rule: &Scoring,
+) -> Vec<(Vec<usize>, Vec<String>)> {
+    order_types
+        .iter()
+        .map(|order_type| {
+            let score = rule.get_score_vector(order_type);
+            let values = score
+                .iter()
+                .map(|x| {
+                    let s = model.eval(x, true).unwrap();
+                    let (a, b) = s.as_real().unwrap();
+                    format!("{}/{}", a, b)
+                })
+                .collect::<Vec<_>>();
+            (order_type.clone(), values)
+        })
+        .collect()
+}
 
+fn print_scoring_system(scoring_system: &[(Vec<usize>, Vec<String>)]) {
+    for (order_type, values) in scoring_system {
         println!("{:?} -> {:?}", order_type, values);
     }
 }
 
+fn scoring_system_json(scoring_system: &[(Vec<usize>, Vec<String>)]) -> Value {
+    scoring_system
+        .iter()
+        .map(|(order_type, values)| json!({ "order_type": order_type, "scores": values }))
+        .collect()
+}
+
+fn sweep_json(sweep: &Sweep) -> Value {
+    json!({
+        "maximal_satisfiable": sweep.maximal_satisfiable,
+        "minimal_unsatisfiable": sweep.minimal_unsatisfiable,
+        "unknown": sweep.unknown,
+        "checked": sweep.checked,
+    })
+}
+
 pub fn run(args: &Args) {
+    let start = Instant::now();
+    let text = args.format == Format::Text;
     let ctx = z3::Context::new(&z3::Config::new());
 
     let m = args.candidates as usize;
 
-    let profile = get_profile(m, &args.ballot, &ctx);
+    let mut profile = get_profile(m, &args.ballot, &ctx);
+    profile.voters = args.voters;
+    profile.max_voters = args.max_voters;
 
     let order_types = profile
         .votes
@@ -55,22 +158,131 @@ pub fn run(args: &Args) {
     let rule = Scoring::create(args.candidates as usize, order_types.clone(), &ctx);
 
     let mut proof = Proof::new(m, &rule, profile);
+    proof.set_limits(limits(args.timeout, args.total_timeout, args.memory));
+
+    let mut axioms = args.axiom.clone();
+    // Scoring systems have no candidate voters, axioms requiring them are left out.
+    if axioms.is_empty() && args.sweep {
+        axioms = all_axiom_names()
+            .into_iter()
+            .map(|(short, _)| short.to_string())
+            .filter(|short| !axiom_requires_candidate_voters(short))
+            .collect();
+    }
+
+    if let Some(axiom) = axioms
+        .iter()
+        .chain(args.implies.iter())
+        .find(|axiom| axiom_requires_candidate_voters(axiom))
+    {
+        eprintln!(
+            "Axiom {} requires the voters to be the candidates, which scoring systems do not support",
+            get_axiom_full_name(axiom)
+        );
+        std::process::exit(1);
+    }
 
-    for axiom in &args.axiom {
-        println!("Adding axiom: {}", axiom);
+    for axiom in &axioms {
+        if text {
+            println!("Adding axiom: {}", axiom);
+        }
         add_axiom(axiom, &mut proof);
     }
 
-    println!("Checking proof");
-    let (result, model) = proof.check();
+    let mut output = json!({
+        "inputs": {
+            "m": m,
+            "rule": "scoring",
+            "ballot": args.ballot,
+            "axioms": axioms.iter().map(|x| get_axiom_short_name(x)).collect::<Vec<_>>(),
+            "implies": args.implies.as_ref().map(|x| get_axiom_short_name(x)),
+            "sweep": args.sweep,
+            "voters": args.voters,
+            "max_voters": args.max_voters,
+            "timeout": args.timeout,
+            "total_timeout": args.total_timeout,
+            "memory": args.memory,
+        },
+    });
+    let check_start = Instant::now();
 
-    match result {
-        SatResult::Sat => {
-            let model = model.unwrap();
-            print_scoring_system(order_types, model, &rule);
+    if args.sweep {
+        if text {
+            println!("Sweeping axiom combinations");
+        }
+        let sweep = sweep(&proof);
+        if text {
+            print_sweep(&sweep);
         }
-        _ => {
-            println!("{:?}", result);
+        output["sweep"] = sweep_json(&sweep);
+    } else {
+        if let Some(implied) = &args.implies {
+            if text {
+                println!("Adding negated axiom: {}", implied);
+            }
+            add_negated_axiom(implied, &mut proof);
+        }
+
+        if text {
+            println!("Checking proof");
         }
+        let check = proof.check();
+        let result = check.result;
+        output["result"] = json::sat_result(result);
+
+        match result {
+            SatResult::Sat => {
+                let scoring_system = scoring_system(&order_types, proof.model().unwrap(), &rule);
+                if text {
+                    if let Some(implied) = &args.implies {
+                        println!(
+                            "The axioms do not imply {}, separating scoring system:",
+                            get_axiom_full_name(implied)
+                        );
+                    }
+                    print_scoring_system(&scoring_system);
+                }
+                output["scoring_system"] = scoring_system_json(&scoring_system);
+            }
+            SatResult::Unsat => {
+                let core = if args.minimal_core {
+                    let subset = proof.minimal_unsat_subset(&check.unsat_core);
+                    if text && !subset.minimal {
+                        println!("The axioms are not proven minimal, some checks were unknown");
+                    }
+                    output["minimal_core_proven"] = json!(subset.minimal);
+                    subset.axioms
+                } else {
+                    check.unsat_core
+                };
+                if text {
+                    match &args.implies {
+                        Some(implied) => {
+                            println!("The axioms imply {}", get_axiom_full_name(implied));
+                            println!("Required axioms: {}", core.join(", "));
+                        }
+                        None => {
+                            println!("{:?}", result);
+                            println!("Conflicting axioms: {}", core.join(", "));
+                        }
+                    }
+                }
+                output["unsat_core"] = json!(core);
+            }
+            _ => {
+                if text {
+                    println!("{:?}", result);
+                }
+            }
+        }
+    }
+
+    if !text {
+        output["timings"] = json!({
+            "setup_ms": (check_start - start).as_millis(),
+            "check_ms": check_start.elapsed().as_millis(),
+            "total_ms": start.elapsed().as_millis(),
+        });
+        json::print("score", output);
     }
 }
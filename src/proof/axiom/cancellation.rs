@@ -0,0 +1,85 @@
+use itertools::Itertools;
+use z3::ast::{Ast, Bool};
+
+use crate::proof::concrete::{pairwise_support, Votes};
+use crate::proof::{profile::Profile, rule::VotingRule};
+
+use super::{Axiom, AxiomType};
+
+pub struct Cancellation {}
+
+impl Axiom for Cancellation {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // Whenever every pairwise comparison is tied, then every partition must win.
+        std::iter::once(move || {
+            log::info!("Checking for cancelling profiles");
+            let cancelling = profile
+                .partitions
+                .iter()
+                .tuple_combinations()
+                .map(|(partition_a, partition_b)| {
+                    let pro = profile.pairwise_support(partition_a, partition_b, None);
+                    let con = profile.pairwise_support(partition_b, partition_a, None);
+                    pro._eq(&con)
+                })
+                .collect::<Vec<_>>();
+            let precondition = Bool::and(ctx, &cancelling.iter().collect::<Vec<_>>());
+
+            let winners = profile
+                .partitions
+                .iter()
+                .map(|partition| rule.winner(partition, profile, None))
+                .collect::<Vec<_>>();
+            let winning_condition = Bool::and(ctx, &winners.iter().collect::<Vec<_>>());
+
+            // Whenever the preconditions are met, then all partitions must win.
+            let formula = precondition.implies(&winning_condition);
+            formula
+        })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        Cancellation::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        let cancelling =
+            profile
+                .partitions
+                .iter()
+                .tuple_combinations()
+                .all(|(partition_a, partition_b)| {
+                    pairwise_support(votes, partition_a, partition_b)
+                        == pairwise_support(votes, partition_b, partition_a)
+                });
+        Some(!cancelling || winners == profile.partitions)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "canc"
+    }
+
+    fn full_name() -> &'static str {
+        "cancellation"
+    }
+}
@@ -1,23 +1,24 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, rc::Rc};
 
 use z3::ast::{Ast, Bool, Int};
 
-use crate::proof::{profile::Profile, rule::VotingRule};
+use crate::proof::concrete::{splits, Votes};
+use crate::proof::{profile::Profile, rule::VotingRule, QuantifiedOutcome};
 use crate::utils::structures::powerset_generator;
 
-use super::{Axiom, AxiomType};
+use super::{Axiom, AxiomType, QuantifiedProfile};
 
 pub struct Consistency {}
 
 impl Axiom for Consistency {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let ctx = profile.get_ctx();
         // Create the subprofiles
-        let sub_profile1 = profile.create_new("'");
-        let sub_profile2 = profile.create_new("''");
+        let sub_profile1 = Rc::new(profile.create_new("'"));
+        let sub_profile2 = Rc::new(profile.create_new("''"));
 
         // The sum of the votes of the subprofiles should be equal to the votes of the original profile
         let sum_correct = profile
@@ -47,48 +48,63 @@ impl Axiom for Consistency {
             &sum_pos2,
             &sum_condition,
         ];
-        let sub_profiles_preconditions = Bool::and(ctx, &precondition_formulas);
+        let sub_profiles_preconditions = Rc::new(Bool::and(ctx, &precondition_formulas));
 
         // For every possible winning set
-        powerset_generator(&profile.partitions).filter_map(move |winning_set| {
-            if winning_set.is_empty() {
-                return None;
-            }
-            log::info!(
-                "Checking for winning set {}",
-                winning_set
-                    .iter()
-                    .map(|x| { x.to_string() })
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-            // The winning set is the intersection of the winning sets of the subprofiles.
-            let winner_set1 = rule.winner_set(&sub_profile1, &winning_set, None);
-            let winner_set2 = rule.winner_set(&sub_profile2, &winning_set, None);
-
-            // The winning set should be exact the intersection, therefore every partition not in the winning set must loose in either subprofile.
-            let partitions = profile.partitions.iter().collect::<BTreeSet<_>>();
-            let looser = partitions.difference(&winning_set).collect::<Vec<_>>();
-            let condition = looser
-                .into_iter()
-                .map(|partition| {
-                    let winner1 = rule.winner(partition, &sub_profile1, None);
-                    let winner2 = rule.winner(partition, &sub_profile2, None);
-                    let cond = Bool::and(ctx, &[&winner1, &winner2]);
-                    cond.not()
-                })
-                .collect::<Vec<_>>();
-            let exact_intersection = Bool::and(ctx, &condition.iter().collect::<Vec<_>>());
-
-            // Whenever this holds, then the intersection (the winning set) should be the winner set of the original profile.
-            let precondition = Bool::and(ctx, &[&winner_set1, &winner_set2, &exact_intersection]);
-            let postcondition = rule.winner_set(profile, &winning_set, None);
-
-            // The formula is (sub_profiles_preconditions and precondition) implies postcondition.
-            let formula = Bool::and(ctx, &[&sub_profiles_preconditions, &precondition])
-                .implies(&postcondition);
-            Some(formula)
-        })
+        powerset_generator(&profile.partitions)
+            .filter(|winning_set| !winning_set.is_empty())
+            .map(move |winning_set| {
+                let sub_profile1 = sub_profile1.clone();
+                let sub_profile2 = sub_profile2.clone();
+                let sub_profiles_preconditions = sub_profiles_preconditions.clone();
+                move || {
+                    log::info!(
+                        "Checking for winning set {}",
+                        winning_set
+                            .iter()
+                            .map(|x| { x.to_string() })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    // The winning set is the intersection of the winning sets of the subprofiles.
+                    let winner_set1 = rule.winner_set(&sub_profile1, &winning_set, None);
+                    let winner_set2 = rule.winner_set(&sub_profile2, &winning_set, None);
+
+                    // The winning set should be exact the intersection, therefore every partition not in the winning set must loose in either subprofile.
+                    let partitions = profile.partitions.iter().collect::<BTreeSet<_>>();
+                    let looser = partitions.difference(&winning_set).collect::<Vec<_>>();
+                    let condition = looser
+                        .into_iter()
+                        .map(|partition| {
+                            let winner1 = rule.winner(partition, &sub_profile1, None);
+                            let winner2 = rule.winner(partition, &sub_profile2, None);
+                            let cond = Bool::and(ctx, &[&winner1, &winner2]);
+                            cond.not()
+                        })
+                        .collect::<Vec<_>>();
+                    let exact_intersection = match condition.len() {
+                        0 => Bool::from_bool(ctx, true),
+                        _ => Bool::and(ctx, &condition.iter().collect::<Vec<_>>()),
+                    };
+
+                    // Whenever this holds, then the intersection (the winning set) should be the winner set of the original profile.
+                    let precondition =
+                        Bool::and(ctx, &[&winner_set1, &winner_set2, &exact_intersection]);
+                    let postcondition = rule.winner_set(profile, &winning_set, None);
+
+                    // The formula is (sub_profiles_preconditions and precondition) implies postcondition.
+                    let formula = Bool::and(ctx, &[&sub_profiles_preconditions, &precondition])
+                        .implies(&postcondition);
+                    formula
+                }
+            })
+    }
+
+    fn quantified_profiles<'a>(profile: &Profile<'a>, _index: usize) -> Vec<QuantifiedProfile<'a>> {
+        vec![
+            QuantifiedProfile::Separate("first part".to_string(), profile.create_new("'")),
+            QuantifiedProfile::Separate("second part".to_string(), profile.create_new("''")),
+        ]
     }
 
     fn get_type() -> AxiomType {
@@ -102,6 +118,35 @@ impl Axiom for Consistency {
         result
     }
 
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        _profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        for (first, second) in splits(votes) {
+            if !holds_for_split(rule, votes, &first, &second)? {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn holds_for_quantified<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+        quantified: &[QuantifiedOutcome],
+    ) -> Option<bool> {
+        match quantified {
+            [first, second] => holds_for_split(rule, votes, &first.profile, &second.profile),
+            _ => Consistency::holds_concretely(rule, profile, votes),
+        }
+    }
+
     fn short_name() -> &'static str {
         "cons"
     }
@@ -110,3 +155,20 @@ impl Axiom for Consistency {
         "consistency"
     }
 }
+
+/// Returns true, if the partitions winning both parts of the split win the whole profile.
+fn holds_for_split<'a>(
+    rule: &dyn VotingRule<'a>,
+    votes: &Votes,
+    first: &Votes,
+    second: &Votes,
+) -> Option<bool> {
+    let winners = rule.concrete_winners(votes)?;
+    let winners1 = rule.concrete_winners(first)?;
+    let winners2 = rule.concrete_winners(second)?;
+    Some(
+        winners1
+            .intersection(&winners2)
+            .all(|x| winners.contains(x)),
+    )
+}
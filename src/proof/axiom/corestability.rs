@@ -0,0 +1,105 @@
+use itertools::Itertools;
+use z3::ast::{Bool, Int};
+
+use crate::proof::{profile::Profile, rule::VotingRule};
+
+use super::{Axiom, AxiomType};
+
+pub struct CoreStability {}
+
+impl Axiom for CoreStability {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        let candidate_votes = profile.candidate_votes.as_ref();
+        if candidate_votes.is_none() {
+            log::error!("Core stability requires a profile where the voters are the candidates, there are no conditions");
+        }
+
+        // A coalition blocks a winning partition if every member strictly prefers every partition containing the coalition.
+        profile
+            .partitions
+            .iter()
+            .cartesian_product(profile.coalitions.iter())
+            .filter(|(partition, coalition)| !partition.contains(coalition))
+            .filter_map(move |(partition, coalition)| {
+                let candidate_votes = candidate_votes?;
+                let deviations = profile
+                    .partitions
+                    .iter()
+                    .filter(|x| x.contains(coalition))
+                    .collect::<Vec<_>>();
+
+                // The votes of every member for a ranking preferring the deviation.
+                let mut deviating_choices = Vec::new();
+                for member in &coalition.members {
+                    let choices = candidate_votes.get(member).unwrap(); // Save, every candidate votes
+                    let member_choices = choices
+                        .iter()
+                        .filter(|(ranking, _)| {
+                            deviations
+                                .iter()
+                                .all(|x| ranking.is_strictly_preferred(x, partition))
+                        })
+                        .map(|(_, var)| var)
+                        .collect::<Vec<_>>();
+
+                    // The member never deviates, therefore the coalition can not block.
+                    if member_choices.is_empty() {
+                        return None;
+                    }
+                    deviating_choices.push(member_choices);
+                }
+
+                Some(move || {
+                    log::info!(
+                        "Checking for coalition {} blocking {}",
+                        coalition.to_string(),
+                        partition.to_string()
+                    );
+                    // Every member of the coalition must vote for a ranking preferring the deviation.
+                    let zero = Int::from_i64(ctx, 0);
+                    let members_deviate = deviating_choices
+                        .iter()
+                        .map(|choices| {
+                            let choices =
+                                choices.iter().map(|var| var.gt(&zero)).collect::<Vec<_>>();
+                            Bool::or(ctx, &choices.iter().collect::<Vec<_>>())
+                        })
+                        .collect::<Vec<_>>();
+                    let blocking = Bool::and(ctx, &members_deviate.iter().collect::<Vec<_>>());
+
+                    let winner = rule.winner(partition, profile, None);
+
+                    // Whenever the partition wins, then the coalition must not block it.
+                    let formula = winner.implies(&blocking.not());
+                    formula
+                })
+            })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        CoreStability::condition_generator(profile, rule).collect()
+    }
+
+    fn requires_candidate_voters() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "core"
+    }
+
+    fn full_name() -> &'static str {
+        "core-stability"
+    }
+}
@@ -1,9 +1,10 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, rc::Rc};
 
 use z3::ast::{Bool, Int};
 
 use crate::structures::Partition;
 
+use crate::proof::concrete::{total_votes, Votes};
 use crate::proof::{profile::Profile, rule::VotingRule};
 
 use super::{Axiom, AxiomType};
@@ -11,16 +12,16 @@ use super::{Axiom, AxiomType};
 pub struct Majority {}
 
 impl Axiom for Majority {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let ctx = profile.get_ctx();
 
         // To have a strict majority, one needs more than halv_votes.
         let all_vars = profile.votes.values().collect::<Vec<_>>();
         let sum = Int::add(ctx, &all_vars);
-        let half_votes = sum.div(&Int::from_i64(ctx, 2));
+        let half_votes = Rc::new(sum.div(&Int::from_i64(ctx, 2)));
 
         // For every partition we calculate the sum of the first priority votes.
         let first_prio_sums = profile
@@ -47,37 +48,42 @@ impl Axiom for Majority {
                 (partition, sum)
             })
             .collect::<BTreeMap<&Partition, Int>>();
+        let first_prio_sums = Rc::new(first_prio_sums);
 
         profile.partitions.iter().map(move |partition| {
-            log::info!("Checking for majority partition {}", partition.to_string());
-            // Precondition
-            // partition must be a strict majority partition
-            // Every other partition must not be a strict majority partition.
-            let mut precondition = Vec::new();
-            let is_majority = first_prio_sums.get(partition).unwrap().gt(&half_votes); // Save, first_prio_sums contains all partitions
-            precondition.push(&is_majority);
-            let only_majority_conditions = profile
-                .partitions
-                .iter()
-                .filter_map(|other| {
-                    if other == partition {
-                        None
-                    } else {
-                        let score = first_prio_sums.get(other).unwrap(); // Save, first_prio_sums contains all partitions
-                        let condition = score.le(&half_votes);
-                        Some(condition)
-                    }
-                })
-                .collect::<Vec<_>>();
-            precondition.extend(only_majority_conditions.iter());
-            let precondition = Bool::and(ctx, &precondition);
-
-            // The partition should be winning.
-            let winner = rule.winner(partition, profile, None);
-
-            // Preconditions implies winner
-            let formula = precondition.implies(&winner);
-            formula
+            let half_votes = half_votes.clone();
+            let first_prio_sums = first_prio_sums.clone();
+            move || {
+                log::info!("Checking for majority partition {}", partition.to_string());
+                // Precondition
+                // partition must be a strict majority partition
+                // Every other partition must not be a strict majority partition.
+                let mut precondition = Vec::new();
+                let is_majority = first_prio_sums.get(partition).unwrap().gt(&half_votes); // Save, first_prio_sums contains all partitions
+                precondition.push(&is_majority);
+                let only_majority_conditions = profile
+                    .partitions
+                    .iter()
+                    .filter_map(|other| {
+                        if other == partition {
+                            None
+                        } else {
+                            let score = first_prio_sums.get(other).unwrap(); // Save, first_prio_sums contains all partitions
+                            let condition = score.le(&half_votes);
+                            Some(condition)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                precondition.extend(only_majority_conditions.iter());
+                let precondition = Bool::and(ctx, &precondition);
+
+                // The partition should be winning.
+                let winner = rule.winner(partition, profile, None);
+
+                // Preconditions implies winner
+                let formula = precondition.implies(&winner);
+                formula
+            }
         })
     }
 
@@ -92,6 +98,40 @@ impl Axiom for Majority {
         Majority::condition_generator(profile, rule).collect()
     }
 
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        let total = total_votes(votes);
+        // If exactly one partition is ranked first by a strict majority, then it must win.
+        let majority = profile
+            .partitions
+            .iter()
+            .filter(|partition| {
+                let first = votes
+                    .iter()
+                    .filter(|(ranking, _)| ranking.index(partition) == 0)
+                    .map(|(_, count)| count)
+                    .sum::<u64>();
+                2 * first > total
+            })
+            .collect::<Vec<_>>();
+        match majority[..] {
+            [partition] => Some(winners.contains(partition)),
+            _ => Some(true),
+        }
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Some(profile.partition_keys())
+    }
+
     fn short_name() -> &'static str {
         "maj"
     }
@@ -1,8 +1,13 @@
+mod cancellation;
 mod consistency;
+mod corestability;
 mod fullcoalitionsupport;
 mod majority;
 mod nonimposition;
 mod participation;
+mod refinement;
+mod resoluteness;
+mod smith;
 mod split;
 mod strategyproof;
 mod strongnonimposition;
@@ -11,11 +16,16 @@ mod unanimity;
 mod weakpairsupport;
 // mod new_axiom;
 
+pub use cancellation::Cancellation;
 pub use consistency::Consistency;
+pub use corestability::CoreStability;
 pub use fullcoalitionsupport::FullCoalitionSupport;
 pub use majority::Majority;
 pub use nonimposition::Nonimposition;
 pub use participation::Participation;
+pub use refinement::{MeetSupport, RefinementDominance};
+pub use resoluteness::Resoluteness;
+pub use smith::{CondorcetLoser, SmithConsistency};
 pub use split::{StrictAllSplit, StrictSomeSplit, WeakAllSplit, WeakSomeSplit};
 pub use strategyproof::Strategyproof;
 pub use strongnonimposition::Strongnonimposition;
@@ -24,17 +34,27 @@ pub use unanimity::Unanimity;
 pub use weakpairsupport::WeakPairSupport;
 // pub use new_axiom::MyAxiom;
 
-use z3::SatResult;
-
 use z3::ast::Bool;
 
-use crate::proof::{profile::Profile, rule::VotingRule, Proof};
+use crate::proof::parallel::{self, BuildProof};
+use crate::proof::{
+    concrete::Votes, profile::Profile, rule::VotingRule, AxiomOutcome, Proof, QuantifiedOutcome,
+};
+use crate::structures::Ranking;
 
 pub enum AxiomType {
     Forall,
     Exists,
 }
 
+/// A profile a condition quantifies over besides the main profile, with a label for reports.
+pub enum QuantifiedProfile<'a> {
+    /// The main profile with one additional voter of the ranking.
+    ExtraVoter(String, Ranking),
+    /// A profile with its own variables.
+    Separate(String, Profile<'a>),
+}
+
 pub trait Axiom {
     /// Creates the Z3 conditions for the axiom.
     /// If the axiom type is forall, then all conditions must be true for the axiom to hold.
@@ -48,10 +68,72 @@ pub trait Axiom {
     fn get_type() -> AxiomType;
 
     /// Generates the conditions for the axiom as an iterator.
+    /// The order must be deterministic, checkpoints refer to the conditions by their index.
     fn condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b;
+    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+        Self::lazy_condition_generator(profile, rule).map(|build| build())
+    }
+
+    /// Generates the conditions in the order of ```condition_generator``` without building them.
+    /// Skipped conditions, e.g. of other workers, are never built.
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b;
+
+    /// Checks the axiom for concrete votes without the solver.
+    /// Returns ```None```, if the axiom or the rule can not be evaluated concretely.
+    fn holds_concretely<'a>(
+        _rule: &dyn VotingRule<'a>,
+        _profile: &Profile<'a>,
+        _votes: &Votes,
+    ) -> Option<bool> {
+        None
+    }
+
+    /// Returns true, if ```holds_concretely``` evaluates the axiom, such that it can be checked by enumerating profiles.
+    fn evaluates_concretely() -> bool {
+        false
+    }
+
+    /// Checks a counterexample of the solver without the solver, given the other profiles its condition quantifies over.
+    /// Returns ```None```, if the axiom or the rule can not be evaluated concretely.
+    fn holds_for_quantified<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+        _quantified: &[QuantifiedOutcome],
+    ) -> Option<bool> {
+        Self::holds_concretely(rule, profile, votes)
+    }
+
+    /// Returns the profiles besides the main profile which condition ```index``` quantifies over.
+    fn quantified_profiles<'a>(
+        _profile: &Profile<'a>,
+        _index: usize,
+    ) -> Vec<QuantifiedProfile<'a>> {
+        Vec::new()
+    }
+
+    /// Returns true, if relabeling the candidates maps the conditions to conditions of the same axiom.
+    fn is_neutral() -> bool {
+        true
+    }
+
+    /// Returns true, if the axiom is only defined for profiles where the voters are the candidates.
+    /// Otherwise the axiom has no conditions.
+    fn requires_candidate_voters() -> bool {
+        false
+    }
+
+    /// Returns a key for every condition in the order of the condition generator, conditions relabeling each other share the key.
+    /// With a neutral rule such conditions hold together, so only the first condition of every key needs to be checked.
+    /// Returns ```None```, if the conditions have no keys.
+    fn canonical_keys(_profile: &Profile) -> Option<Vec<String>> {
+        None
+    }
 
     /// Returns the short name of the axiom.
     fn short_name() -> &'static str;
@@ -71,6 +153,17 @@ macro_rules! case_distinction {
     }
 }
 
+macro_rules! case_distinction_parallel {
+    ($func:ident, [$($type:ty),*], $name:expr, $args:tt) => {
+        $(
+            if $name.eq_ignore_ascii_case(<$type>::short_name()) || $name.eq_ignore_ascii_case(<$type>::full_name()){
+                return parallel::$func::<$type>$args;
+            }
+
+        )*
+    }
+}
+
 macro_rules! all_names {
     ([$($axiom:ty),*]) => {
 
@@ -113,11 +206,37 @@ macro_rules! get_axiom_name {
             eprintln!("Axiom {} not found", name);
             std::process::exit(1)
         }
+
+        /// Returns true, if the axiom given its name can be evaluated without the solver.
+        pub fn axiom_evaluates_concretely(name :&str) -> bool {
+
+            $(
+                if name.eq_ignore_ascii_case(<$axiom>::short_name()) || name.eq_ignore_ascii_case(<$axiom>::full_name()){
+                    return <$axiom>::evaluates_concretely();
+                }
+            )*
+
+            eprintln!("Axiom {} not found", name);
+            std::process::exit(1)
+        }
+
+        /// Returns true, if the axiom given its name requires the voters to be the candidates.
+        pub fn axiom_requires_candidate_voters(name :&str) -> bool {
+
+            $(
+                if name.eq_ignore_ascii_case(<$axiom>::short_name()) || name.eq_ignore_ascii_case(<$axiom>::full_name()){
+                    return <$axiom>::requires_candidate_voters();
+                }
+            )*
+
+            eprintln!("Axiom {} not found", name);
+            std::process::exit(1)
+        }
     }
 }
 
 macro_rules! create_functions {
-    ([$(($func:ident, $ret:ty)),*], $type:tt) => {
+    ([$(($func:ident, $ret:ty)),*], [$(($pfunc:ident, $pret:ty, ($($arg:ident: $argty:ty),*))),*], $type:tt) => {
 
         all_names!($type);
 
@@ -131,11 +250,32 @@ $(pub fn $func(axiom: &str, proof: &mut Proof) -> $ret {
     std::process::exit(1)
     }
 )*
+
+$(pub fn $pfunc(axiom: &str, $($arg: $argty),*) -> $pret {
+
+    case_distinction_parallel!($pfunc, $type, axiom, ($($arg),*));
+
+    eprintln!("Axiom {} not found", axiom);
+    std::process::exit(1)
+    }
+)*
 }
 }
 
 create_functions!(
-    [(check_iteratively, SatResult), (add_axiom, ())],
+    [
+        (check_iteratively, AxiomOutcome),
+        (add_axiom, ()),
+        (add_negated_axiom, ()),
+        (condition_smt2, Vec<String>),
+        (brute_force, AxiomOutcome),
+        (smallest_electorate, (AxiomOutcome, Option<u64>))
+    ],
+    [(
+        check_iteratively_parallel,
+        Result<AxiomOutcome, String>,
+        (workers: usize, build: &BuildProof)
+    )],
     [
         Unanimity,
         Consistency,
@@ -150,7 +290,14 @@ create_functions!(
         WeakSomeSplit,
         WeakAllSplit,
         WeakPairSupport,
-        StrongPairSupport
+        StrongPairSupport,
+        SmithConsistency,
+        CondorcetLoser,
+        RefinementDominance,
+        MeetSupport,
+        CoreStability,
+        Resoluteness,
+        Cancellation
         // ,MyAxiom
     ]
 );
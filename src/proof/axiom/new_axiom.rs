@@ -10,11 +10,11 @@ use super::{Axiom, AxiomType};
 pub struct MyAxiom {}
 
 impl Axiom for MyAxiom {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
-        [0..1].iter().map(|_|{
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        [0..1].iter().map(|_| || {
             todo!()
         }
         )
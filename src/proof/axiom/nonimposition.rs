@@ -7,14 +7,16 @@ use super::{Axiom, AxiomType};
 pub struct Nonimposition {}
 
 impl Axiom for Nonimposition {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         // Every partition should be able to win.
         profile.partitions.iter().map(move |partition| {
-            log::info!("Checking for winner: {}", partition.to_string());
-            rule.only_winner(partition, profile, None)
+            move || {
+                log::info!("Checking for winner: {}", partition.to_string());
+                rule.only_winner(partition, profile, None)
+            }
         })
     }
 
@@ -29,6 +31,10 @@ impl Axiom for Nonimposition {
         Nonimposition::condition_generator(profile, rule).collect()
     }
 
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Some(profile.partition_keys())
+    }
+
     fn short_name() -> &'static str {
         "noim"
     }
@@ -1,10 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use z3::ast::{Bool, Int};
 
+use crate::proof::concrete::{with_vote, Votes};
 use crate::proof::{profile::Profile, rule::VotingRule};
 
-use super::{Axiom, AxiomType};
+use crate::structures::{Partition, Ranking};
+
+use super::{Axiom, AxiomType, QuantifiedProfile};
 
 pub struct Participation {}
 
@@ -20,35 +23,16 @@ impl Axiom for Participation {
         AxiomType::Forall
     }
 
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let ctx = profile.get_ctx();
-        // Iterate over all possible rankings (this is the ranking from the new voter)
-        profile.votes.iter().flat_map(move |(ranking, _)| {
-            log::info!("Checking for voter with ranking {}", ranking.to_string());
-            // Create a new profile with the new ranking
-            let one = Int::from_i64(ctx, 1);
-            let extra_votes = BTreeMap::from([(ranking.clone(), one)]);
-            // Iterate over all partitions. In each iteration we assume that partition has won the election with the old profile.
-            profile.partitions.iter().filter_map(move |partition| {
-                log::info!("Winner without voter {}", partition.to_string());
-                // Winner of the old profile
-                let winner_condition = rule.only_winner(partition, profile, None);
-
-                // Check which partitions are strictly less preferred by the new voter.
-                let less_preferred = profile
-                    .partitions
-                    .iter()
-                    .filter(|other| ranking.is_strictly_preferred(partition, other))
-                    .collect::<Vec<_>>();
-
-                // If no partition is strictly less preferred, then the axiom holds trivially and we can skip this case.
-                if less_preferred.is_empty() {
-                    log::info!("None partition are less preferred");
-                    None
-                } else {
+        cases(&profile.votes, &profile.partitions).map(
+            move |(ranking, partition, less_preferred)| {
+                move || {
+                    log::info!("Checking for voter with ranking {}", ranking.to_string());
+                    log::info!("Winner without voter {}", partition.to_string());
                     log::info!(
                         "Less preferred {}",
                         less_preferred
@@ -57,6 +41,11 @@ impl Axiom for Participation {
                             .collect::<Vec<_>>()
                             .join(", ")
                     );
+                    // Create a new profile with the new ranking
+                    let extra_votes = BTreeMap::from([(ranking.clone(), Int::from_i64(ctx, 1))]);
+                    // Winner of the old profile
+                    let winner_condition = rule.only_winner(partition, profile, None);
+
                     // We want that none of the less preferred partitions win the election with the new profile.
                     let more_preferred_does_not_win = less_preferred
                         .iter()
@@ -65,12 +54,50 @@ impl Axiom for Participation {
                     let more_preferred_does_not_win =
                         Bool::or(ctx, &more_preferred_does_not_win.iter().collect::<Vec<_>>())
                             .not();
-                    let condition = winner_condition.implies(&more_preferred_does_not_win);
-                    Some(condition)
+                    winner_condition.implies(&more_preferred_does_not_win)
                 }
+            },
+        )
+    }
+
+    fn quantified_profiles<'a>(profile: &Profile<'a>, index: usize) -> Vec<QuantifiedProfile<'a>> {
+        cases(&profile.votes, &profile.partitions)
+            .nth(index)
+            .map(|(ranking, _, _)| {
+                let label = format!("with voter {}", ranking.to_string());
+                QuantifiedProfile::ExtraVoter(label, ranking.clone())
             })
-        })
+            .into_iter()
+            .collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        if winners.len() != 1 {
+            return Some(true);
+        }
+        let winner = winners.first().unwrap();
+
+        // A new voter must not make a partition the only winner, which they like less than the old winner.
+        for ranking in profile.votes.keys() {
+            let new_winners = rule.concrete_winners(&with_vote(votes, ranking))?;
+            if new_winners.len() == 1
+                && ranking.is_strictly_preferred(winner, new_winners.first().unwrap())
+            {
+                return Some(false);
+            }
+        }
+        Some(true)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
     }
+
     fn short_name() -> &'static str {
         "part"
     }
@@ -79,3 +106,21 @@ impl Axiom for Participation {
         "participation"
     }
 }
+
+/// Returns the cases of the conditions in order: the ranking of the new voter, the only winner without them and the partitions they like less.
+/// Cases without a less preferred partition hold trivially and are left out.
+fn cases<'b, V: 'b>(
+    votes: &'b BTreeMap<Ranking, V>,
+    partitions: &'b BTreeSet<Partition>,
+) -> impl Iterator<Item = (&'b Ranking, &'b Partition, Vec<&'b Partition>)> + 'b {
+    votes.keys().flat_map(move |ranking| {
+        partitions.iter().filter_map(move |partition| {
+            // Check which partitions are strictly less preferred by the new voter.
+            let less_preferred = partitions
+                .iter()
+                .filter(|other| ranking.is_strictly_preferred(partition, other))
+                .collect::<Vec<_>>();
+            (!less_preferred.is_empty()).then_some((ranking, partition, less_preferred))
+        })
+    })
+}
@@ -0,0 +1,221 @@
+use itertools::Itertools;
+use z3::ast::Bool;
+
+use crate::proof::concrete::{voted_rankings, Votes};
+use crate::proof::{profile::Profile, rule::VotingRule};
+
+use super::{Axiom, AxiomType};
+
+pub struct RefinementDominance {}
+
+impl Axiom for RefinementDominance {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // Whenever every voter strictly prefers a refinement of a partition, then the partition must not win.
+        profile
+            .partitions
+            .iter()
+            .cartesian_product(profile.partitions.iter())
+            .filter(|(fine, coarse)| fine.is_strict_refinement_of(coarse))
+            .map(move |(fine, coarse)| {
+                move || {
+                    log::info!(
+                        "Checking for refinement {} of {}",
+                        fine.to_string(),
+                        coarse.to_string()
+                    );
+
+                    // All voters who do not strictly prefer the refinement must not exist.
+                    let preconditions = profile
+                        .votes
+                        .keys()
+                        .filter_map(|ranking| {
+                            if ranking.is_strictly_preferred(fine, coarse) {
+                                None
+                            } else {
+                                Some(rule.zero_votes(ranking, profile, None))
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    let precondition = if preconditions.is_empty() {
+                        Bool::from_bool(ctx, true)
+                    } else {
+                        Bool::and(ctx, &preconditions.iter().collect::<Vec<_>>())
+                    };
+
+                    let not_winning = rule.not_winner(coarse, profile, None);
+
+                    // Whenever the preconditions are met, then the coarser partition must not win.
+                    let formula = precondition.implies(&not_winning);
+                    formula
+                }
+            })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        RefinementDominance::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        // If every voter strictly prefers a refinement of a partition, then the partition must not win.
+        let holds = profile
+            .partitions
+            .iter()
+            .cartesian_product(profile.partitions.iter())
+            .filter(|(fine, coarse)| fine.is_strict_refinement_of(coarse))
+            .all(|(fine, coarse)| {
+                let dominated = voted_rankings(votes)
+                    .all(|ranking| ranking.is_strictly_preferred(fine, coarse));
+                !dominated || !winners.contains(coarse)
+            });
+        Some(holds)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "refdom"
+    }
+
+    fn full_name() -> &'static str {
+        "refinement-dominance"
+    }
+}
+
+pub struct MeetSupport {}
+
+impl Axiom for MeetSupport {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // Whenever a partition is the meet of all partitions ranked first, then this partition must win.
+        profile.partitions.iter().filter_map(move |partition| {
+            // Every pair separated in the meet must be separated by some partition ranked first.
+            let mut separating_rankings = Vec::new();
+            for pair in profile.candidates.iter().tuple_combinations() {
+                if partition.contains_pair(&pair) {
+                    continue;
+                }
+                let separating = profile
+                    .votes
+                    .keys()
+                    .filter(|ranking| ranking.ranking[0].iter().any(|x| !x.contains_pair(&pair)))
+                    .collect::<Vec<_>>();
+
+                // The partition can never be the meet.
+                if separating.is_empty() {
+                    return None;
+                }
+                separating_rankings.push(separating);
+            }
+
+            Some(move || {
+                log::info!("Checking for meet {}", partition.to_string());
+
+                // All voters who rank a partition first, which is not a coarsening of the meet, must not exist.
+                let coarsening = profile
+                    .votes
+                    .keys()
+                    .filter_map(|ranking| {
+                        if ranking.ranking[0]
+                            .iter()
+                            .all(|top| partition.is_refinement_of(top))
+                        {
+                            None
+                        } else {
+                            Some(rule.zero_votes(ranking, profile, None))
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let separated = separating_rankings
+                    .iter()
+                    .map(|rankings| {
+                        let separating_voters = rankings
+                            .iter()
+                            .map(|ranking| rule.zero_votes(ranking, profile, None).not())
+                            .collect::<Vec<_>>();
+                        Bool::or(ctx, &separating_voters.iter().collect::<Vec<_>>())
+                    })
+                    .collect::<Vec<_>>();
+
+                let preconditions = coarsening
+                    .iter()
+                    .chain(separated.iter())
+                    .collect::<Vec<_>>();
+                let precondition = if preconditions.is_empty() {
+                    Bool::from_bool(ctx, true)
+                } else {
+                    Bool::and(ctx, &preconditions)
+                };
+
+                let winning = rule.winner(partition, profile, None);
+
+                // Whenever the preconditions are met, then the meet must win.
+                let formula = precondition.implies(&winning);
+                formula
+            })
+        })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        MeetSupport::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        // If a partition is the meet of all partitions ranked first, then it must win.
+        let meet = voted_rankings(votes)
+            .flat_map(|ranking| ranking.ranking[0].iter())
+            .cloned()
+            .reduce(|meet, top| meet.meet(&top));
+        let holds = match meet {
+            Some(meet) if profile.partitions.contains(&meet) => winners.contains(&meet),
+            _ => true,
+        };
+        Some(holds)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "meet"
+    }
+
+    fn full_name() -> &'static str {
+        "meet-support"
+    }
+}
@@ -0,0 +1,67 @@
+use itertools::Itertools;
+use z3::ast::Bool;
+
+use crate::proof::concrete::Votes;
+use crate::proof::{profile::Profile, rule::VotingRule};
+
+use super::{Axiom, AxiomType};
+
+pub struct Resoluteness {}
+
+impl Axiom for Resoluteness {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // There is always a winner, hence the winner is unique if no two partitions win together.
+        profile
+            .partitions
+            .iter()
+            .tuple_combinations()
+            .map(move |(partition_a, partition_b)| {
+                move || {
+                    log::info!(
+                        "Checking for tie between {} and {}",
+                        partition_a.to_string(),
+                        partition_b.to_string()
+                    );
+                    let winner_a = rule.winner(partition_a, profile, None);
+                    let winner_b = rule.winner(partition_b, profile, None);
+                    let formula = Bool::and(ctx, &[&winner_a, &winner_b]).not();
+                    formula
+                }
+            })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        Resoluteness::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        _profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        Some(rule.concrete_winners(votes)?.len() <= 1)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "res"
+    }
+
+    fn full_name() -> &'static str {
+        "resoluteness"
+    }
+}
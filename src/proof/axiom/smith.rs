@@ -0,0 +1,163 @@
+use std::collections::BTreeSet;
+
+use z3::ast::Bool;
+
+use crate::proof::concrete::{pairwise_majority, smith_set, Votes};
+use crate::proof::{profile::Profile, rule::VotingRule};
+use crate::utils::structures::powerset_generator;
+
+use super::{Axiom, AxiomType};
+
+pub struct SmithConsistency {}
+
+impl Axiom for SmithConsistency {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // The dominating sets are nested, hence the Smith set is the intersection of all dominating sets.
+        // Therefore every partition outside of a dominating set must not win.
+        powerset_generator(&profile.partitions)
+            .filter(|dominating_set| {
+                !dominating_set.is_empty() && dominating_set.len() != profile.partitions.len()
+            })
+            .map(move |dominating_set| {
+                move || {
+                    log::info!(
+                        "Checking for dominating set {}",
+                        dominating_set
+                            .iter()
+                            .map(|x| { x.to_string() })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    let partitions = profile.partitions.iter().collect::<BTreeSet<_>>();
+                    let others = partitions.difference(&dominating_set).collect::<Vec<_>>();
+
+                    // Every partition in the set beats every partition outside of the set.
+                    let dominating = dominating_set
+                        .iter()
+                        .flat_map(|partition| {
+                            others
+                                .iter()
+                                .map(|other| profile.pairwise_majority(partition, other, None))
+                        })
+                        .collect::<Vec<_>>();
+                    let precondition = Bool::and(ctx, &dominating.iter().collect::<Vec<_>>());
+
+                    // No partition outside of the set wins.
+                    let not_winning = others
+                        .iter()
+                        .map(|other| rule.not_winner(other, profile, None))
+                        .collect::<Vec<_>>();
+                    let winning_condition = Bool::and(ctx, &not_winning.iter().collect::<Vec<_>>());
+
+                    // Whenever the set is dominating, then the winners must be in the set.
+                    let formula = precondition.implies(&winning_condition);
+                    formula
+                }
+            })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        SmithConsistency::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        Some(winners.is_subset(&smith_set(votes, &profile.partitions)))
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "smith"
+    }
+
+    fn full_name() -> &'static str {
+        "smith-consistency"
+    }
+}
+
+pub struct CondorcetLoser {}
+
+impl Axiom for CondorcetLoser {
+    fn lazy_condition_generator<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        let ctx = profile.get_ctx();
+        // A partition which is beaten by every other partition must not win.
+        profile.partitions.iter().map(move |partition| {
+            move || {
+                log::info!("Checking for condorcet loser {}", partition.to_string());
+                let beaten = profile
+                    .partitions
+                    .iter()
+                    .filter(|other| *other != partition)
+                    .map(|other| profile.pairwise_majority(other, partition, None))
+                    .collect::<Vec<_>>();
+                let precondition = Bool::and(ctx, &beaten.iter().collect::<Vec<_>>());
+
+                let not_winning = rule.not_winner(partition, profile, None);
+
+                // Whenever the partition is a condorcet loser, then it must not win.
+                let formula = precondition.implies(&not_winning);
+                formula
+            }
+        })
+    }
+
+    fn get_type() -> AxiomType {
+        AxiomType::Forall
+    }
+
+    fn condition<'a: 'b, 'b>(
+        profile: &'b Profile<'a>,
+        rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
+    ) -> Vec<Bool<'a>> {
+        CondorcetLoser::condition_generator(profile, rule).collect()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        // A partition beaten by every other partition must not win.
+        let holds = winners.iter().all(|winner| {
+            profile
+                .partitions
+                .iter()
+                .any(|other| other != winner && !pairwise_majority(votes, other, winner))
+        });
+        Some(holds)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn short_name() -> &'static str {
+        "closer"
+    }
+
+    fn full_name() -> &'static str {
+        "condorcet-loser"
+    }
+}
@@ -4,15 +4,18 @@ use z3::ast::Bool;
 
 use crate::structures::Coalition;
 
+use crate::proof::concrete::{voted_rankings, Votes};
 use crate::proof::{profile::Profile, rule::VotingRule};
 
 use super::{Axiom, AxiomType};
 
+#[derive(Clone, Copy)]
 enum SplitType {
     Weak,
     Strict,
 }
 
+#[derive(Clone, Copy)]
 enum WinningType {
     All,
     Some,
@@ -105,41 +108,97 @@ impl Split {
         Bool::or(ctx, &conditions)
     }
 
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         split_type: SplitType,
         winning_type: WinningType,
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let _ctx = profile.get_ctx();
 
-        profile.coalitions.iter().filter_map(move |coalition| {
-            log::info!("Checking for Split {}", coalition.to_string());
-            if coalition.members.is_empty() || coalition.members.len() == profile.num_candidates {
-                return None;
-            }
-            let precondition = match split_type {
-                SplitType::Weak => Split::profile_weak_split(profile, rule, coalition.clone()),
-                SplitType::Strict => Split::profile_strict_split(profile, rule, coalition.clone()),
-            };
-            let winner_condition = match winning_type {
-                WinningType::All => Split::winner_all_split(profile, rule, coalition.clone()),
-                WinningType::Some => Split::winner_some_split(profile, rule, coalition.clone()),
-            };
-            let formula = precondition.implies(&winner_condition);
-            Some(formula)
-        })
+        profile
+            .coalitions
+            .iter()
+            .filter(|coalition| {
+                !coalition.members.is_empty() && coalition.members.len() != profile.num_candidates
+            })
+            .map(move |coalition| {
+                move || {
+                    log::info!("Checking for Split {}", coalition.to_string());
+                    let precondition = match split_type {
+                        SplitType::Weak => {
+                            Split::profile_weak_split(profile, rule, coalition.clone())
+                        }
+                        SplitType::Strict => {
+                            Split::profile_strict_split(profile, rule, coalition.clone())
+                        }
+                    };
+                    let winner_condition = match winning_type {
+                        WinningType::All => {
+                            Split::winner_all_split(profile, rule, coalition.clone())
+                        }
+                        WinningType::Some => {
+                            Split::winner_some_split(profile, rule, coalition.clone())
+                        }
+                    };
+                    let formula = precondition.implies(&winner_condition);
+                    formula
+                }
+            })
+    }
+
+    fn holds_concretely<'a>(
+        split_type: SplitType,
+        winning_type: WinningType,
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        // If every voter splits the coalition, then all or some winners must split it.
+        let holds = profile
+            .coalitions
+            .iter()
+            .filter(|coalition| {
+                !coalition.members.is_empty() && coalition.members.len() != profile.num_candidates
+            })
+            .all(|coalition| {
+                let split = voted_rankings(votes).all(|ranking| match split_type {
+                    SplitType::Weak => ranking.is_weakly_split(coalition),
+                    SplitType::Strict => ranking.is_strict_split(coalition),
+                });
+                !split
+                    || match winning_type {
+                        WinningType::All => winners.iter().all(|x| x.is_split(coalition)),
+                        WinningType::Some => winners.iter().any(|x| x.is_split(coalition)),
+                    }
+            });
+        Some(holds)
+    }
+
+    /// Returns the keys of the conditions, the coalitions are filtered like in ```lazy_condition_generator```.
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        let keys = profile
+            .coalitions
+            .iter()
+            .zip(profile.coalition_keys())
+            .filter(|(coalition, _)| {
+                !coalition.members.is_empty() && coalition.members.len() != profile.num_candidates
+            })
+            .map(|(_, key)| key)
+            .collect();
+        Some(keys)
     }
 }
 
 pub struct WeakSomeSplit {}
 
 impl Axiom for WeakSomeSplit {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
-        Split::condition_generator(SplitType::Weak, WinningType::Some, profile, rule)
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        Split::lazy_condition_generator(SplitType::Weak, WinningType::Some, profile, rule)
     }
 
     fn get_type() -> AxiomType {
@@ -152,6 +211,22 @@ impl Axiom for WeakSomeSplit {
     ) -> Vec<Bool<'a>> {
         WeakSomeSplit::condition_generator(profile, rule).collect()
     }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        Split::holds_concretely(SplitType::Weak, WinningType::Some, rule, profile, votes)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Split::canonical_keys(profile)
+    }
     fn short_name() -> &'static str {
         "splitws"
     }
@@ -164,11 +239,11 @@ impl Axiom for WeakSomeSplit {
 pub struct WeakAllSplit {}
 
 impl Axiom for WeakAllSplit {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
-        Split::condition_generator(SplitType::Weak, WinningType::All, profile, rule)
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        Split::lazy_condition_generator(SplitType::Weak, WinningType::All, profile, rule)
     }
 
     fn get_type() -> AxiomType {
@@ -181,6 +256,22 @@ impl Axiom for WeakAllSplit {
     ) -> Vec<Bool<'a>> {
         WeakAllSplit::condition_generator(profile, rule).collect()
     }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        Split::holds_concretely(SplitType::Weak, WinningType::All, rule, profile, votes)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Split::canonical_keys(profile)
+    }
     fn short_name() -> &'static str {
         "splitwa"
     }
@@ -193,11 +284,11 @@ impl Axiom for WeakAllSplit {
 pub struct StrictSomeSplit {}
 
 impl Axiom for StrictSomeSplit {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
-        Split::condition_generator(SplitType::Strict, WinningType::Some, profile, rule)
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        Split::lazy_condition_generator(SplitType::Strict, WinningType::Some, profile, rule)
     }
 
     fn get_type() -> AxiomType {
@@ -210,6 +301,22 @@ impl Axiom for StrictSomeSplit {
     ) -> Vec<Bool<'a>> {
         StrictSomeSplit::condition_generator(profile, rule).collect()
     }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        Split::holds_concretely(SplitType::Strict, WinningType::Some, rule, profile, votes)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Split::canonical_keys(profile)
+    }
     fn short_name() -> &'static str {
         "splitss"
     }
@@ -222,11 +329,11 @@ impl Axiom for StrictSomeSplit {
 pub struct StrictAllSplit {}
 
 impl Axiom for StrictAllSplit {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
-        Split::condition_generator(SplitType::Strict, WinningType::All, profile, rule)
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
+        Split::lazy_condition_generator(SplitType::Strict, WinningType::All, profile, rule)
     }
 
     fn get_type() -> AxiomType {
@@ -239,6 +346,22 @@ impl Axiom for StrictAllSplit {
     ) -> Vec<Bool<'a>> {
         StrictAllSplit::condition_generator(profile, rule).collect()
     }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        Split::holds_concretely(SplitType::Strict, WinningType::All, rule, profile, votes)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
+    fn canonical_keys(profile: &Profile) -> Option<Vec<String>> {
+        Split::canonical_keys(profile)
+    }
     fn short_name() -> &'static str {
         "splitsa"
     }
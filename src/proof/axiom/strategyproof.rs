@@ -1,11 +1,14 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use itertools::Itertools;
 use z3::ast::{Bool, Int};
 
+use crate::proof::concrete::{with_vote, Votes};
 use crate::proof::{profile::Profile, rule::VotingRule};
 
-use super::{Axiom, AxiomType};
+use crate::structures::{Partition, Ranking};
+
+use super::{Axiom, AxiomType, QuantifiedProfile};
 
 pub struct Strategyproof {}
 
@@ -21,52 +24,85 @@ impl Axiom for Strategyproof {
         AxiomType::Forall
     }
 
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let ctx = profile.get_ctx();
-        profile
-            .votes
-            .keys()
-            .cartesian_product(profile.votes.keys())
-            .filter(|(a, b)| a != b)
-            .flat_map(move |(true_pref, fake_pref)| {
-                log::info!("Checking for preferences:");
-                log::info!("true: {}", true_pref.to_string());
-                log::info!("fake: {}", fake_pref.to_string());
-
-                let extra_votes_true = BTreeMap::from([(true_pref.clone(), Int::from_i64(ctx, 1))]);
-                let extra_votes_fake = BTreeMap::from([(fake_pref.clone(), Int::from_i64(ctx, 1))]);
-
-                profile.partitions.iter().filter_map(move |partition| {
+        cases(&profile.votes, &profile.partitions).map(
+            move |(true_pref, fake_pref, partition, strictly_more_preferred)| {
+                move || {
+                    log::info!("Checking for preferences:");
+                    log::info!("true: {}", true_pref.to_string());
+                    log::info!("fake: {}", fake_pref.to_string());
                     log::info!("True winner {}", partition.to_string());
-                    let winner_true = rule.only_winner(partition, profile, Some(&extra_votes_true));
 
-                    let strictly_more_preferred = profile
-                        .partitions
+                    let extra_votes_true =
+                        BTreeMap::from([(true_pref.clone(), Int::from_i64(ctx, 1))]);
+                    let extra_votes_fake =
+                        BTreeMap::from([(fake_pref.clone(), Int::from_i64(ctx, 1))]);
+
+                    let winner_true = rule.only_winner(partition, profile, Some(&extra_votes_true));
+                    let winning_conditions = strictly_more_preferred
                         .iter()
-                        .filter(|other| true_pref.is_strictly_preferred(other, partition))
+                        .map(|p| rule.only_winner(p, profile, Some(&extra_votes_fake)).not())
                         .collect::<Vec<_>>();
 
-                    if strictly_more_preferred.is_empty() {
-                        log::info!("No more preferred alternative");
-                        None
-                    } else {
-                        let winning_conditions = strictly_more_preferred
-                            .iter()
-                            .map(|p| rule.only_winner(p, profile, Some(&extra_votes_fake)).not())
-                            .collect::<Vec<_>>();
-
-                        let winning_conditions =
-                            Bool::and(ctx, &winning_conditions.iter().collect::<Vec<_>>());
-
-                        let condition = winner_true.implies(&winning_conditions);
-                        Some(condition)
-                    }
-                })
+                    let winning_conditions =
+                        Bool::and(ctx, &winning_conditions.iter().collect::<Vec<_>>());
+
+                    winner_true.implies(&winning_conditions)
+                }
+            },
+        )
+    }
+
+    fn quantified_profiles<'a>(profile: &Profile<'a>, index: usize) -> Vec<QuantifiedProfile<'a>> {
+        cases(&profile.votes, &profile.partitions)
+            .nth(index)
+            .map(|(true_pref, fake_pref, _, _)| {
+                vec![
+                    QuantifiedProfile::ExtraVoter(
+                        format!("with true ranking {}", true_pref.to_string()),
+                        true_pref.clone(),
+                    ),
+                    QuantifiedProfile::ExtraVoter(
+                        format!("with fake ranking {}", fake_pref.to_string()),
+                        fake_pref.clone(),
+                    ),
+                ]
             })
+            .unwrap_or_default()
+    }
+
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        // The only winner after a new voter submits each ranking.
+        let mut only_winners = BTreeMap::new();
+        for ranking in profile.votes.keys() {
+            let winners = rule.concrete_winners(&with_vote(votes, ranking))?;
+            if winners.len() == 1 {
+                only_winners.insert(ranking, winners.into_iter().next().unwrap());
+            }
+        }
+
+        // A voter must not get a strictly preferred only winner by submitting a fake ranking.
+        let manipulable = only_winners
+            .iter()
+            .cartesian_product(only_winners.iter())
+            .any(|((true_pref, winner_true), (fake_pref, winner_fake))| {
+                true_pref != fake_pref && true_pref.is_strictly_preferred(winner_fake, winner_true)
+            });
+        Some(!manipulable)
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
     }
+
     fn short_name() -> &'static str {
         "strat"
     }
@@ -75,3 +111,29 @@ impl Axiom for Strategyproof {
         "strategyproof"
     }
 }
+
+/// Returns the cases of the conditions in order: the true and the fake ranking of the new voter, the only winner with the true ranking and the partitions the voter prefers over it.
+/// Cases without a more preferred partition hold trivially and are left out.
+fn cases<'b, V: 'b>(
+    votes: &'b BTreeMap<Ranking, V>,
+    partitions: &'b BTreeSet<Partition>,
+) -> impl Iterator<Item = (&'b Ranking, &'b Ranking, &'b Partition, Vec<&'b Partition>)> + 'b {
+    votes
+        .keys()
+        .cartesian_product(votes.keys())
+        .filter(|(a, b)| a != b)
+        .flat_map(move |(true_pref, fake_pref)| {
+            partitions.iter().filter_map(move |partition| {
+                let strictly_more_preferred = partitions
+                    .iter()
+                    .filter(|other| true_pref.is_strictly_preferred(other, partition))
+                    .collect::<Vec<_>>();
+                (!strictly_more_preferred.is_empty()).then_some((
+                    true_pref,
+                    fake_pref,
+                    partition,
+                    strictly_more_preferred,
+                ))
+            })
+        })
+}
@@ -8,26 +8,26 @@ use super::{Axiom, AxiomType};
 pub struct Strongnonimposition {}
 
 impl Axiom for Strongnonimposition {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         // Every non-empty set must be able to win.
-        powerset_generator(&profile.partitions).filter_map(move |winner_set| {
-            if winner_set.is_empty() {
-                None
-            } else {
-                log::info!(
-                    "Checking for winning set: {}",
-                    winner_set
-                        .iter()
-                        .map(|x| { x.to_string() })
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-                Some(rule.exact_winner_set(&winner_set, profile, None))
-            }
-        })
+        powerset_generator(&profile.partitions)
+            .filter(|winner_set| !winner_set.is_empty())
+            .map(move |winner_set| {
+                move || {
+                    log::info!(
+                        "Checking for winning set: {}",
+                        winner_set
+                            .iter()
+                            .map(|x| { x.to_string() })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    rule.exact_winner_set(&winner_set, profile, None)
+                }
+            })
     }
 
     fn get_type() -> AxiomType {
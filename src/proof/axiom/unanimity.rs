@@ -1,5 +1,8 @@
 use z3::ast::Bool;
 
+use std::collections::BTreeSet;
+
+use crate::proof::concrete::{voted_rankings, Votes};
 use crate::proof::{profile::Profile, rule::VotingRule};
 use crate::utils::structures::powerset_generator;
 
@@ -8,47 +11,48 @@ use super::{Axiom, AxiomType};
 pub struct Unanimity {}
 
 impl Axiom for Unanimity {
-    fn condition_generator<'a: 'b, 'b>(
+    fn lazy_condition_generator<'a: 'b, 'b>(
         profile: &'b Profile<'a>,
         rule: &'b Box<&'b (dyn VotingRule<'a> + 'b)>,
-    ) -> impl Iterator<Item = Bool<'a>> + 'b {
+    ) -> impl Iterator<Item = impl FnOnce() -> Bool<'a> + 'b> + 'b {
         let ctx = profile.get_ctx();
         // Whenever a set is ranked first by all voters, then this set must win.
-        powerset_generator(&profile.partitions).filter_map(move |winning_set| {
-            if winning_set.is_empty() {
-                return None;
-            }
-            log::info!(
-                "Checking set {:?}",
-                winning_set
-                    .iter()
-                    .map(|x| { x.to_string() })
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-            // All voters must rank the winning_set as their first indifference class.
-            let preconditions = profile
-                .votes
-                .iter()
-                .filter_map(|(ranking, _)| {
-                    if !ranking.is_exacty_equiv_class(0, &winning_set) {
-                        let zero_votes = rule.zero_votes(ranking, profile, None);
-                        Some(zero_votes)
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            let preconditions = preconditions.iter().collect::<Vec<_>>();
-            let precondition = Bool::and(ctx, &preconditions);
+        powerset_generator(&profile.partitions)
+            .filter(|winning_set| !winning_set.is_empty())
+            .map(move |winning_set| {
+                move || {
+                    log::info!(
+                        "Checking set {:?}",
+                        winning_set
+                            .iter()
+                            .map(|x| { x.to_string() })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    // All voters must rank the winning_set as their first indifference class.
+                    let preconditions = profile
+                        .votes
+                        .iter()
+                        .filter_map(|(ranking, _)| {
+                            if !ranking.is_exacty_equiv_class(0, &winning_set) {
+                                let zero_votes = rule.zero_votes(ranking, profile, None);
+                                Some(zero_votes)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let preconditions = preconditions.iter().collect::<Vec<_>>();
+                    let precondition = Bool::and(ctx, &preconditions);
 
-            // This winning_set must be the exact winner.
-            let winning_condition = rule.exact_winner_set(&winning_set, profile, None);
+                    // This winning_set must be the exact winner.
+                    let winning_condition = rule.exact_winner_set(&winning_set, profile, None);
 
-            // Whenever the preconditions are met, then the winning_condition must hold.
-            let formula = precondition.implies(&winning_condition);
-            Some(formula)
-        })
+                    // Whenever the preconditions are met, then the winning_condition must hold.
+                    let formula = precondition.implies(&winning_condition);
+                    formula
+                }
+            })
     }
 
     fn get_type() -> AxiomType {
@@ -62,6 +66,26 @@ impl Axiom for Unanimity {
         Unanimity::condition_generator(profile, rule).collect()
     }
 
+    fn holds_concretely<'a>(
+        rule: &dyn VotingRule<'a>,
+        _profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<bool> {
+        let winners = rule.concrete_winners(votes)?;
+        // If all voters rank the same set first, then exactly this set must win.
+        let first = voted_rankings(votes)
+            .map(|ranking| &ranking.ranking[0])
+            .collect::<BTreeSet<_>>();
+        match first.len() {
+            1 => Some(first.into_iter().all(|first| first == &winners)),
+            _ => Some(true),
+        }
+    }
+
+    fn evaluates_concretely() -> bool {
+        true
+    }
+
     fn short_name() -> &'static str {
         "unam"
     }
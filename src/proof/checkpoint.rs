@@ -0,0 +1,112 @@
+use std::{
+    collections::BTreeSet,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// The progress of an iterative proof of one axiom.
+/// The file starts with a line describing the settings, followed by the index of every condition which held.
+pub struct Checkpoint {
+    path: PathBuf,
+    file: File,
+    completed: BTreeSet<usize>,
+}
+
+impl Checkpoint {
+    /// Opens the checkpoint at ```path```, creating the directories if necessary.
+    /// If ```resume``` is true and the checkpoint was written with the same ```settings```, the completed conditions are loaded.
+    /// Otherwise the checkpoint starts empty.
+    pub fn open(path: &Path, settings: &str, resume: bool) -> io::Result<Self> {
+        let completed = if resume {
+            Self::load(path, settings)?
+        } else {
+            None
+        };
+
+        let file = match &completed {
+            Some((_, length)) => {
+                // The incomplete last line is cut off, such that the next index starts on a new line.
+                let file = OpenOptions::new().append(true).open(path)?;
+                file.set_len(*length)?;
+                file
+            }
+            None => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut file = File::create(path)?;
+                writeln!(file, "{}", settings)?;
+                file
+            }
+        };
+
+        Ok(Checkpoint {
+            path: path.to_path_buf(),
+            file,
+            completed: completed
+                .map(|(completed, _)| completed)
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Reads the completed conditions together with the length of the complete lines.
+    /// Returns ```None``` if there is no checkpoint for the settings.
+    /// An incomplete last line of an interrupted run is ignored.
+    fn load(path: &Path, settings: &str) -> io::Result<Option<(BTreeSet<usize>, u64)>> {
+        if !path.exists() {
+            log::warn!(
+                "No checkpoint found at {}, starting from the beginning",
+                path.display()
+            );
+            return Ok(None);
+        }
+        let content = fs::read(path)?;
+        let length = content
+            .iter()
+            .rposition(|&x| x == b'\n')
+            .map_or(0, |x| x + 1);
+        let content = String::from_utf8_lossy(&content[..length]);
+        let mut lines = content.lines();
+        match lines.next() {
+            Some(header) if header == settings => {}
+            _ => {
+                log::warn!("The checkpoint {} was written with different settings, starting from the beginning", path.display());
+                return Ok(None);
+            }
+        }
+        let completed = lines
+            .filter_map(|line| line.trim().parse::<usize>().ok())
+            .collect::<BTreeSet<_>>();
+        log::warn!("Resuming with {} completed conditions", completed.len());
+        Ok(Some((completed, length as u64)))
+    }
+
+    /// Returns true, if the condition with index ```index``` held in a previous run.
+    pub fn is_completed(&self, index: usize) -> bool {
+        self.completed.contains(&index)
+    }
+
+    /// Stores that the condition with index ```index``` held.
+    pub fn record(&mut self, index: usize) {
+        self.completed.insert(index);
+        if let Err(err) = writeln!(self.file, "{}", index).and_then(|_| self.file.flush()) {
+            log::error!(
+                "Failed to write checkpoint {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+
+    /// Deletes the checkpoint, once the axiom is decided there is nothing left to resume.
+    pub fn remove(self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            log::error!(
+                "Failed to remove checkpoint {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
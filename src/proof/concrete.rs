@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
+
+use crate::structures::{Partition, Ranking};
+
+/// The number of votes per ranking of a concrete profile.
+pub type Votes = BTreeMap<Ranking, u64>;
+
+/// Returns the rankings which receive at least one vote.
+pub fn voted_rankings(votes: &Votes) -> impl Iterator<Item = &Ranking> {
+    votes
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .map(|(ranking, _)| ranking)
+}
+
+/// Returns the total number of votes.
+pub fn total_votes(votes: &Votes) -> u64 {
+    votes.values().sum()
+}
+
+/// Returns the votes with one additional vote for ```ranking```.
+pub fn with_vote(votes: &Votes, ranking: &Ranking) -> Votes {
+    let mut votes = votes.clone();
+    *votes.entry(ranking.clone()).or_insert(0) += 1;
+    votes
+}
+
+/// Returns the number of votes which strictly prefer ```partition_a``` over ```partition_b```.
+pub fn pairwise_support(votes: &Votes, partition_a: &Partition, partition_b: &Partition) -> u64 {
+    votes
+        .iter()
+        .filter(|(ranking, _)| ranking.is_strictly_preferred(partition_a, partition_b))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// Returns true, if a strict majority prefers ```partition_a``` over ```partition_b```.
+pub fn pairwise_majority(votes: &Votes, partition_a: &Partition, partition_b: &Partition) -> bool {
+    pairwise_support(votes, partition_a, partition_b)
+        > pairwise_support(votes, partition_b, partition_a)
+}
+
+/// Returns the Smith set, the smallest set of partitions beating every partition outside of it.
+/// A partition is in the Smith set iff it reaches every partition by a chain of partitions not beaten by their successor.
+pub fn smith_set(votes: &Votes, partitions: &BTreeSet<Partition>) -> BTreeSet<Partition> {
+    partitions
+        .iter()
+        .filter(|start| {
+            let mut reached = BTreeSet::from([*start]);
+            let mut frontier = vec![*start];
+            while let Some(current) = frontier.pop() {
+                for next in partitions {
+                    if !reached.contains(next) && !pairwise_majority(votes, next, current) {
+                        reached.insert(next);
+                        frontier.push(next);
+                    }
+                }
+            }
+            reached.len() == partitions.len()
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns all splits of the votes into two non-empty profiles.
+pub fn splits(votes: &Votes) -> Vec<(Votes, Votes)> {
+    let voted = votes
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .collect::<Vec<_>>();
+
+    let mut splits = vec![(Votes::new(), Votes::new())];
+    for (ranking, count) in voted {
+        splits = splits
+            .into_iter()
+            .flat_map(|(first, second)| {
+                (0..=*count).map(move |taken| {
+                    let mut first = first.clone();
+                    let mut second = second.clone();
+                    if taken > 0 {
+                        first.insert(ranking.clone(), taken);
+                    }
+                    if taken < *count {
+                        second.insert(ranking.clone(), count - taken);
+                    }
+                    (first, second)
+                })
+            })
+            .collect();
+    }
+    splits
+        .into_iter()
+        .filter(|(first, second)| !first.is_empty() && !second.is_empty())
+        .collect()
+}
+
+/// Returns every profile with exactly ```voters``` voters over ```rankings```.
+pub fn profiles(rankings: &[Ranking], voters: u64) -> impl Iterator<Item = Votes> + '_ {
+    rankings
+        .iter()
+        .combinations_with_replacement(voters as usize)
+        .map(|chosen| {
+            chosen.into_iter().fold(Votes::new(), |mut votes, ranking| {
+                *votes.entry(ranking.clone()).or_insert(0) += 1;
+                votes
+            })
+        })
+}
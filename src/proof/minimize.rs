@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use z3::{
+    ast::{Ast, Bool, Int},
+    Model, SatResult, Solver,
+};
+
+use crate::proof::profile::Profile;
+use crate::structures::Ranking;
+
+/// Returns the number of votes per ranking given the model, rankings without votes are omitted.
+pub fn votes_of(profile: &Profile, model: &Model) -> BTreeMap<Ranking, u64> {
+    profile
+        .votes
+        .iter()
+        .filter_map(|(ranking, var)| {
+            let val = model.eval(var, true)?.as_u64()?;
+            (val > 0).then(|| (ranking.clone(), val))
+        })
+        .collect()
+}
+
+/// Returns the Z3 condition that the profile has exactly the votes ```votes```.
+fn fixed_votes<'ctx>(profile: &Profile<'ctx>, votes: &BTreeMap<Ranking, u64>) -> Bool<'ctx> {
+    let ctx = profile.get_ctx();
+    let conditions = profile
+        .votes
+        .iter()
+        .map(|(ranking, var)| {
+            let val = votes.get(ranking).copied().unwrap_or(0);
+            var._eq(&Int::from_u64(ctx, val))
+        })
+        .collect::<Vec<_>>();
+    Bool::and(ctx, &conditions.iter().collect::<Vec<_>>())
+}
+
+/// Searches a model of ```formula``` with the fewest voters and among those with the fewest distinct rankings.
+/// ```limit``` sets the timeout of the next check and returns false, if no time is left.
+/// If a check is not decided, the smallest model found so far is returned.
+pub fn minimize<'ctx>(
+    profile: &Profile<'ctx>,
+    formula: &Bool<'ctx>,
+    limit: &dyn Fn(&Solver<'ctx>) -> bool,
+) -> Option<Model<'ctx>> {
+    let ctx = profile.get_ctx();
+    let zero = Int::from_i64(ctx, 0);
+    let one = Int::from_i64(ctx, 1);
+
+    let used_rankings = profile
+        .votes
+        .values()
+        .map(|var| var.gt(&zero).ite(&one, &zero))
+        .collect::<Vec<_>>();
+    let used_rankings = Int::add(ctx, &used_rankings.iter().collect::<Vec<_>>());
+
+    let solver = Solver::new(ctx);
+    solver.assert(formula);
+    let mut model = None;
+
+    // The objectives are minimized lexicographically, each is fixed to its minimum before the next.
+    for objective in [profile.total_votes(), used_rankings] {
+        let mut bound = None;
+        loop {
+            if !limit(&solver) {
+                log::info!("Failed to minimize: total timeout");
+                return model;
+            }
+            solver.push();
+            if let Some(bound) = bound {
+                solver.assert(&objective.lt(&Int::from_u64(ctx, bound)));
+            }
+            let result = solver.check();
+            let found = solver.get_model();
+            solver.pop(1);
+            match result {
+                SatResult::Sat => {
+                    let found = found?;
+                    bound = Some(found.eval(&objective, true)?.as_u64()?);
+                    model = Some(found);
+                }
+                SatResult::Unsat => break,
+                SatResult::Unknown => {
+                    log::info!("Failed to minimize: {:?}", solver.get_reason_unknown());
+                    return model;
+                }
+            }
+        }
+        solver.assert(&objective._eq(&Int::from_u64(ctx, bound?)));
+    }
+    model
+}
+
+/// Greedily removes rankings and single voters from ```votes``` as long as ```formula``` stays satisfiable.
+/// Returns the shrunk votes together with a model of ```formula``` for them.
+/// A check which is not decided within ```limit``` keeps the votes.
+pub fn shrink<'ctx>(
+    profile: &Profile<'ctx>,
+    formula: &Bool<'ctx>,
+    votes: BTreeMap<Ranking, u64>,
+    limit: &dyn Fn(&Solver<'ctx>) -> bool,
+) -> (BTreeMap<Ranking, u64>, Option<Model<'ctx>>) {
+    let ctx = profile.get_ctx();
+    let check = |votes: &BTreeMap<Ranking, u64>| {
+        let solver = Solver::new(ctx);
+        if !limit(&solver) {
+            return None;
+        }
+        solver.assert(formula);
+        solver.assert(&fixed_votes(profile, votes));
+        match solver.check() {
+            SatResult::Sat => solver.get_model(),
+            _ => None,
+        }
+    };
+
+    let mut votes = votes;
+    let mut model = check(&votes);
+    if model.is_none() {
+        return (votes, None);
+    }
+
+    let rankings = votes.keys().cloned().collect::<Vec<_>>();
+    for ranking in rankings {
+        // First try to drop the ranking, then to remove its voters one by one.
+        let mut candidate = votes.clone();
+        candidate.remove(&ranking);
+        if let Some(shrunk) = check(&candidate) {
+            log::info!("Removed ranking {}", ranking.to_string());
+            votes = candidate;
+            model = Some(shrunk);
+            continue;
+        }
+
+        while votes[&ranking] > 1 {
+            let mut candidate = votes.clone();
+            *candidate.get_mut(&ranking).unwrap() -= 1;
+            match check(&candidate) {
+                Some(shrunk) => {
+                    votes = candidate;
+                    model = Some(shrunk);
+                }
+                None => break,
+            }
+        }
+    }
+    (votes, model)
+}
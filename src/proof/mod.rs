@@ -1,6 +1,13 @@
 pub mod axiom;
+pub mod checkpoint;
+pub mod concrete;
+pub mod minimize;
+pub mod parallel;
 pub mod profile;
 pub mod proof;
 pub mod rule;
+pub mod sweep;
 
-pub use proof::Proof;
+pub use proof::{
+    AxiomOutcome, ConditionOutcome, Limits, Proof, ProofResult, QuantifiedOutcome, UnsatSubset,
+};
@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use z3::{Config, Context, SatResult};
+
+use crate::proof::{axiom::Axiom, AxiomOutcome, Proof};
+
+/// Builds a proof in the given context, every worker builds its own proof.
+pub type BuildProof<'b> = dyn for<'ctx> Fn(&'ctx Context) -> Proof<'ctx> + Sync + 'b;
+
+/// The share of the conditions checked by one worker.
+/// Worker ```worker``` checks the conditions whose index is ```worker``` modulo ```workers```.
+pub struct Shard<'s> {
+    worker: usize,
+    workers: usize,
+    first_failure: &'s AtomicUsize,
+}
+
+impl Shard<'_> {
+    /// Returns true, if the condition with index ```index``` belongs to this worker.
+    pub fn owns(&self, index: usize) -> bool {
+        index % self.workers == self.worker
+    }
+
+    /// Returns true, if a condition before ```index``` already failed and the condition does not need to be checked.
+    pub fn is_obsolete(&self, index: usize) -> bool {
+        index > self.first_failure.load(Ordering::SeqCst)
+    }
+
+    /// Reports that the condition with index ```index``` failed.
+    pub fn report_failure(&self, index: usize) {
+        self.first_failure.fetch_min(index, Ordering::SeqCst);
+    }
+}
+
+/// Merges the outcomes of the workers, such that the result is the same as checking all conditions in order.
+/// Returns an error, if there are no outcomes.
+fn merge(mut outcomes: Vec<AxiomOutcome>) -> Result<AxiomOutcome, String> {
+    let name = outcomes
+        .first()
+        .ok_or("at least one worker is required")?
+        .name;
+    let mut conditions = outcomes
+        .iter_mut()
+        .flat_map(|outcome| outcome.conditions.drain(..))
+        .collect::<Vec<_>>();
+    conditions.sort_by_key(|condition| condition.index);
+
+    let first_failure = conditions
+        .iter()
+        .position(|condition| condition.held == Some(false));
+    let result = match first_failure {
+        Some(position) => {
+            conditions.truncate(position + 1);
+            SatResult::Unsat
+        }
+        None if outcomes.iter().any(|x| x.result == SatResult::Unknown) => SatResult::Unknown,
+        None => SatResult::Sat,
+    };
+
+    Ok(AxiomOutcome {
+        name,
+        result,
+        conditions,
+    })
+}
+
+/// Checks the axiom with ```workers``` threads, each with its own context and proof.
+/// The conditions are split by their index, once a condition fails the workers skip all later conditions.
+/// Returns an error, if there are no workers.
+pub fn check_iteratively_parallel<A: Axiom>(
+    workers: usize,
+    build: &BuildProof,
+) -> Result<AxiomOutcome, String> {
+    let first_failure = AtomicUsize::new(usize::MAX);
+
+    let outcomes = std::thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|worker| {
+                let first_failure = &first_failure;
+                scope.spawn(move || {
+                    let ctx = Context::new(&Config::new());
+                    let mut proof = build(&ctx);
+                    let shard = Shard {
+                        worker,
+                        workers,
+                        first_failure,
+                    };
+                    proof.check_shard::<A>(&shard)
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    merge(outcomes)
+}
@@ -1,16 +1,19 @@
+use std::cell::RefCell;
 use std::collections::BTreeSet;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
 
-use std::collections::BTreeMap;
-
+use itertools::Itertools;
 use z3::ast::Ast;
 use z3::ast::{Bool, Int};
 use z3::Context;
 
 use crate::ballots::Ballot;
+use crate::proof::rule::{RuleId, ScoreTable};
 
-use crate::structures::Candidate;
 use crate::structures::Coalition;
 use crate::structures::Structure;
+use crate::structures::{canonical, Candidate};
 use crate::structures::{Partition, Ranking};
 
 #[derive(Debug)]
@@ -20,6 +23,26 @@ pub struct Profile<'ctx> {
     pub coalitions: BTreeSet<Coalition>,
     pub candidates: BTreeSet<Candidate>,
     pub num_candidates: usize,
+    /// The votes of every candidate, if the voters are the candidates themselves.
+    pub candidate_votes: Option<BTreeMap<Candidate, BTreeMap<Ranking, Int<'ctx>>>>,
+    /// The exact number of voters, if bounded.
+    pub voters: Option<u64>,
+    /// The maximal number of voters, if bounded.
+    pub max_voters: Option<u64>,
+    /// The score terms of the partitions per rule and extra votes, see ```score_table```.
+    score_tables: RefCell<HashMap<RuleId, ScoreTables<'ctx>>>,
+}
+
+/// The maximal number of score tables with extra votes kept per rule.
+const MAX_EXTRA_SCORE_TABLES: usize = 8;
+
+/// The score tables of one rule.
+#[derive(Debug, Default)]
+struct ScoreTables<'ctx> {
+    /// The table without extra votes.
+    plain: Option<Rc<ScoreTable<'ctx>>>,
+    /// The tables of the most recently used extra votes, the most recent last.
+    extra: Vec<(BTreeMap<Ranking, Int<'ctx>>, Rc<ScoreTable<'ctx>>)>,
 }
 
 impl<'ctx> Profile<'ctx> {
@@ -27,7 +50,6 @@ impl<'ctx> Profile<'ctx> {
         let partitions = Partition::all(m);
         let coalitions = Coalition::all(m);
         let candidates = Candidate::all(m);
-        let num_partitions = partitions.len();
 
         if votes.is_empty() {
             eprintln!("Error: No votes provided");
@@ -49,7 +71,11 @@ impl<'ctx> Profile<'ctx> {
             partitions,
             coalitions,
             candidates,
-            num_candidates: num_partitions,
+            num_candidates: m,
+            candidate_votes: None,
+            voters: None,
+            max_voters: None,
+            score_tables: RefCell::default(),
         }
     }
 
@@ -71,7 +97,54 @@ impl<'ctx> Profile<'ctx> {
             coalitions,
             candidates,
             num_candidates: m,
+            candidate_votes: None,
+            voters: None,
+            max_voters: None,
+            score_tables: RefCell::default(),
+        }
+    }
+
+    /// Returns the score table of ```rule``` with ```extra_votes```, it is computed with ```compute``` if it is not stored.
+    /// The table without extra votes is kept, of the tables with extra votes only the most recently used ones.
+    pub fn score_table(
+        &self,
+        rule: RuleId,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'ctx>>>,
+        compute: impl FnOnce() -> ScoreTable<'ctx>,
+    ) -> Rc<ScoreTable<'ctx>> {
+        if let Some(tables) = self.score_tables.borrow_mut().get_mut(&rule) {
+            match extra_votes {
+                None => {
+                    if let Some(table) = &tables.plain {
+                        return table.clone();
+                    }
+                }
+                Some(extra_votes) => {
+                    if let Some(position) = tables.extra.iter().position(|(x, _)| x == extra_votes)
+                    {
+                        let entry = tables.extra.remove(position);
+                        let table = entry.1.clone();
+                        tables.extra.push(entry);
+                        return table;
+                    }
+                }
+            }
         }
+
+        // The table is computed without borrowing the tables, the rule may request other tables meanwhile.
+        let table = Rc::new(compute());
+        let mut score_tables = self.score_tables.borrow_mut();
+        let tables = score_tables.entry(rule).or_default();
+        match extra_votes {
+            None => tables.plain = Some(table.clone()),
+            Some(extra_votes) => {
+                if tables.extra.len() == MAX_EXTRA_SCORE_TABLES {
+                    tables.extra.remove(0);
+                }
+                tables.extra.push((extra_votes.clone(), table.clone()));
+            }
+        }
+        table
     }
 
     /// Returns the Z3 context of the profile.
@@ -79,9 +152,58 @@ impl<'ctx> Profile<'ctx> {
         self.votes.iter().next().unwrap().1.get_ctx() // The profile can never be empty.
     }
 
+    /// Turns the profile into a profile where the voters are the candidates.
+    /// Every candidate submits exactly one of the rankings, the votes of a ranking are the number of candidates submitting it.
+    pub fn with_candidate_voters(self) -> Self {
+        let ctx = self.get_ctx();
+
+        let candidate_votes = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                let choices = self
+                    .votes
+                    .keys()
+                    .enumerate()
+                    .map(|(i, ranking)| {
+                        let var = Int::new_const(ctx, format!("k_{}_{}", i, candidate.name));
+                        (ranking.clone(), var)
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                (candidate.clone(), choices)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let votes = self
+            .votes
+            .keys()
+            .map(|ranking| {
+                let vars = candidate_votes
+                    .values()
+                    .map(|choices| choices.get(ranking).unwrap()) // Save, every candidate has a variable for every ranking
+                    .collect::<Vec<_>>();
+                (ranking.clone(), Int::add(ctx, &vars))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        Profile {
+            votes,
+            candidate_votes: Some(candidate_votes),
+            score_tables: RefCell::default(),
+            ..self
+        }
+    }
+
     /// Returns all variables in the profile.
+    /// If the voters are the candidates, these are the choices of the candidates.
     pub fn all_vars(&self) -> Vec<&Int<'ctx>> {
-        self.votes.values().collect::<Vec<_>>()
+        match &self.candidate_votes {
+            Some(candidate_votes) => candidate_votes
+                .values()
+                .flat_map(|choices| choices.values())
+                .collect::<Vec<_>>(),
+            None => self.votes.values().collect::<Vec<_>>(),
+        }
     }
 
     /// Returns the Z3 condition that all variables are non-negative.
@@ -107,8 +229,185 @@ impl<'ctx> Profile<'ctx> {
         sum.gt(&zero)
     }
 
+    /// Returns the Z3 condition that every candidate submits exactly one ranking.
+    /// If the voters are not the candidates, the condition is trivially true.
+    pub fn vars_candidate_voters(&self) -> Bool<'ctx> {
+        let ctx = self.get_ctx();
+        match &self.candidate_votes {
+            Some(candidate_votes) => {
+                let one = Int::from_i64(ctx, 1);
+                let conditions = candidate_votes
+                    .values()
+                    .map(|choices| {
+                        let vars = choices.values().collect::<Vec<_>>();
+                        Int::add(ctx, &vars)._eq(&one)
+                    })
+                    .collect::<Vec<_>>();
+                Bool::and(ctx, &conditions.iter().collect::<Vec<_>>())
+            }
+            None => Bool::from_bool(ctx, true),
+        }
+    }
+
+    /// Returns the total number of votes of the profile.
+    pub fn total_votes(&self) -> Int<'ctx> {
+        let ctx = self.get_ctx();
+        Int::add(ctx, &self.votes.values().collect::<Vec<_>>())
+    }
+
+    /// Returns the Z3 condition that the number of voters is within the bounds of the profile.
+    /// If the profile is unbounded, the condition is trivially true.
+    pub fn vars_electorate_size(&self) -> Bool<'ctx> {
+        let ctx = self.get_ctx();
+        let total = self.total_votes();
+        let mut conditions = Vec::new();
+        if let Some(voters) = self.voters {
+            conditions.push(total._eq(&Int::from_u64(ctx, voters)));
+        }
+        if let Some(max_voters) = self.max_voters {
+            conditions.push(total.le(&Int::from_u64(ctx, max_voters)));
+        }
+        match conditions.len() {
+            0 => Bool::from_bool(ctx, true),
+            _ => Bool::and(ctx, &conditions.iter().collect::<Vec<_>>()),
+        }
+    }
+
+    /// Returns the Z3 condition every valid profile has to satisfy.
+    pub fn preconditions(&self) -> Bool<'ctx> {
+        let ctx = self.get_ctx();
+        let non_negative = self.vars_nonnegative();
+        let sum_positive = self.vars_sum_positive();
+        let candidate_voters = self.vars_candidate_voters();
+        let electorate_size = self.vars_electorate_size();
+        Bool::and(
+            ctx,
+            &[
+                &non_negative,
+                &sum_positive,
+                &candidate_voters,
+                &electorate_size,
+            ],
+        )
+    }
+
+    /// Returns true, if relabeling the candidates maps every ranking of the profile to a ranking of the profile.
+    /// If the voters are the candidates, the profile is not closed, as relabeling the rankings does not relabel the voters.
+    pub fn closed_under_relabeling(&self) -> bool {
+        self.candidate_votes.is_none()
+            && Candidate::permutations(self.num_candidates)
+                .iter()
+                .all(|permutation| {
+                    self.votes
+                        .keys()
+                        .all(|ranking| self.votes.contains_key(&ranking.permute(permutation)))
+                })
+    }
+
+    /// Returns the canonical key of every ordered pair of distinct candidates, in the order of ```candidates``` × ```candidates```.
+    pub fn pair_keys(&self) -> Vec<String> {
+        let permutations = Candidate::permutations(self.num_candidates);
+        self.candidates
+            .iter()
+            .cartesian_product(self.candidates.iter())
+            .filter(|pair| pair.0 != pair.1)
+            .map(|(a, b)| {
+                let (a, b) = canonical(&permutations, |x| (a.permute(x), b.permute(x)));
+                format!("{}, {}", a.to_string(), b.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns the canonical key of every partition of the profile.
+    pub fn partition_keys(&self) -> Vec<String> {
+        let permutations = Candidate::permutations(self.num_candidates);
+        self.partitions
+            .iter()
+            .map(|partition| canonical(&permutations, |x| partition.permute(x)).to_string())
+            .collect()
+    }
+
+    /// Returns the canonical key of every coalition of the profile.
+    pub fn coalition_keys(&self) -> Vec<String> {
+        let permutations = Candidate::permutations(self.num_candidates);
+        self.coalitions
+            .iter()
+            .map(|coalition| canonical(&permutations, |x| coalition.permute(x)).to_string())
+            .collect()
+    }
+
+    /// Returns the condition that the votes are lexicographically the largest among all relabelings of the candidates.
+    /// Every profile has a relabeling satisfying it, so a neutral rule violates a neutral axiom iff such a profile violates it.
+    /// Returns ```None```, if the rankings are not closed under relabeling or the voters are the candidates.
+    pub fn symmetry_breaking(&self) -> Option<Bool<'ctx>> {
+        if !self.closed_under_relabeling() {
+            return None;
+        }
+        let ctx = self.get_ctx();
+        let mut conditions = Vec::new();
+        for permutation in Candidate::permutations(self.num_candidates).iter().skip(1) {
+            let permuted = self
+                .votes
+                .keys()
+                .map(|ranking| &self.votes[&ranking.permute(permutation)])
+                .collect::<Vec<_>>();
+            // Built from the last ranking, such that the first differing ranking decides.
+            let mut condition = Bool::from_bool(ctx, true);
+            for (var, other) in self.votes.values().zip(permuted).rev() {
+                if var == other {
+                    continue;
+                }
+                let equal = Bool::and(ctx, &[&var._eq(other), &condition]);
+                condition = Bool::or(ctx, &[&var.gt(other), &equal]);
+            }
+            conditions.push(condition);
+        }
+        match conditions.len() {
+            0 => Some(Bool::from_bool(ctx, true)),
+            _ => Some(Bool::and(ctx, &conditions.iter().collect::<Vec<_>>())),
+        }
+    }
+
+    /// Returns the number of votes which strictly prefer ```partition_a``` over ```partition_b```.
+    /// The extra votes are added to the votes of the profile.
+    pub fn pairwise_support(
+        &self,
+        partition_a: &Partition,
+        partition_b: &Partition,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'ctx>>>,
+    ) -> Int<'ctx> {
+        let ctx = self.get_ctx();
+        let mut variables = Vec::new();
+        self.votes.iter().for_each(|(ranking, var)| {
+            if ranking.is_strictly_preferred(partition_a, partition_b) {
+                variables.push(var);
+                if let Some(extra_vote) = extra_votes.and_then(|x| x.get(ranking)) {
+                    variables.push(extra_vote);
+                }
+            }
+        });
+
+        if variables.is_empty() {
+            Int::from_i64(ctx, 0)
+        } else {
+            Int::add(ctx, &variables)
+        }
+    }
+
+    /// Returns the condition that ```partition_a``` beats ```partition_b``` in a pairwise majority comparison.
+    pub fn pairwise_majority(
+        &self,
+        partition_a: &Partition,
+        partition_b: &Partition,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'ctx>>>,
+    ) -> Bool<'ctx> {
+        let pro = self.pairwise_support(partition_a, partition_b, extra_votes);
+        let con = self.pairwise_support(partition_b, partition_a, extra_votes);
+        pro.gt(&con)
+    }
+
     /// Creates a new profile with the same rankings, however different variables.
-    /// The extra votes are discarded.
+    /// The extra votes are discarded, the voters are not the candidates anymore and the number of voters is unbounded.
     /// ```s``` is the prefix of the variables
     pub fn create_new(&self, s: &str) -> Self {
         let partitions = self.partitions.clone();
@@ -129,6 +428,10 @@ impl<'ctx> Profile<'ctx> {
             coalitions,
             candidates,
             num_candidates,
+            candidate_votes: None,
+            voters: None,
+            max_voters: None,
+            score_tables: RefCell::default(),
         }
     }
 }
@@ -1,38 +1,176 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
+
 use z3::{
-    ast::{exists_const, forall_const, Ast, Bool},
-    Model, SatResult, Solver,
+    ast::{exists_const, forall_const, Ast, Bool, Int},
+    Model, Params, SatResult, Solver,
 };
 
 use crate::proof::{
-    axiom::{Axiom, AxiomType},
+    axiom::{Axiom, AxiomType, QuantifiedProfile},
+    checkpoint::Checkpoint,
+    concrete,
+    minimize::{minimize, shrink, votes_of},
+    parallel::Shard,
     profile::Profile,
     rule::VotingRule,
 };
+use crate::structures::{Partition, Ranking};
+
+/// The outcome of checking a single condition of an axiom.
+#[derive(Debug, Clone)]
+pub struct ConditionOutcome {
+    /// Position of the condition in the order of the condition generator.
+    pub index: usize,
+    /// Whether the condition held, ```None``` if the solver could not decide it.
+    pub held: Option<bool>,
+    /// The counterexample of a forall axiom or the witness of an exists axiom.
+    pub profile: Option<BTreeMap<Ranking, u64>>,
+    /// The winners of ```profile```.
+    pub winners: Option<BTreeSet<Partition>>,
+    /// Whether evaluating ```profile``` without the solver confirms the outcome, ```None``` if it can not be evaluated.
+    pub verified: Option<bool>,
+    /// The scores of all partitions in ```profile``` evaluated without the solver.
+    pub scores: Option<BTreeMap<Partition, f64>>,
+    /// The reason given by the solver, if the condition could not be decided.
+    pub reason: Option<String>,
+    /// The other profiles the condition quantifies over, evaluated in the model.
+    pub quantified: Vec<QuantifiedOutcome>,
+}
+
+/// A profile a condition quantifies over besides the main profile, evaluated in a model.
+#[derive(Debug, Clone)]
+pub struct QuantifiedOutcome {
+    /// Describes the profile relative to the main profile.
+    pub label: String,
+    /// The votes of the profile.
+    pub profile: BTreeMap<Ranking, u64>,
+    /// The winners of ```profile```.
+    pub winners: BTreeSet<Partition>,
+}
+
+/// Resource limits of the solver.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Timeout of a single check in milliseconds.
+    pub condition_timeout: Option<u32>,
+    /// Timeout of all checks together in milliseconds.
+    pub total_timeout: Option<u64>,
+    /// Maximal memory used by Z3 in megabytes.
+    pub memory: Option<u32>,
+}
+
+/// The outcome of checking an axiom.
+#[derive(Debug, Clone)]
+pub struct AxiomOutcome {
+    pub name: &'static str,
+    pub result: SatResult,
+    /// The conditions checked so far, empty if the axiom was checked together with other axioms.
+    pub conditions: Vec<ConditionOutcome>,
+}
 
-#[derive(Debug)]
-pub enum ProofResult {
-    Sat(Vec<String>),
-    Unsat(Vec<String>),
-    Unknown,
+impl AxiomOutcome {
+    /// Returns the condition which did not hold, if any.
+    pub fn failed_condition(&self) -> Option<&ConditionOutcome> {
+        self.conditions.iter().find(|x| x.held == Some(false))
+    }
+}
+
+/// The outcome of a proof.
+#[derive(Debug, Clone)]
+pub struct ProofResult {
+    pub result: SatResult,
+    /// If the axioms are checked together and are unsatisfiable, only the axioms of the unsat core are marked unsatisfiable and the others unknown.
+    pub axioms: Vec<AxiomOutcome>,
+    pub unsat_core: Vec<&'static str>,
+    /// The reason given by the solver, if the axioms could not be checked together.
+    pub reason: Option<String>,
+}
+
+impl ProofResult {
+    /// Combines the outcomes of axioms checked one after another.
+    pub fn from_outcomes(axioms: Vec<AxiomOutcome>) -> Self {
+        let result = if axioms.iter().any(|x| x.result == SatResult::Unsat) {
+            SatResult::Unsat
+        } else if axioms.iter().any(|x| x.result == SatResult::Unknown) {
+            SatResult::Unknown
+        } else {
+            SatResult::Sat
+        };
+        ProofResult {
+            result,
+            axioms,
+            unsat_core: Vec::new(),
+            reason: None,
+        }
+    }
+}
+
+/// An unsatisfiable subset of the axioms shrunk from an unsat core.
+#[derive(Debug, Clone)]
+pub struct UnsatSubset {
+    pub axioms: Vec<&'static str>,
+    /// Whether removing any axiom was shown to make the others satisfiable, false if some check was unknown.
+    pub minimal: bool,
+}
+
+/// The outcome of checking several axioms together.
+struct AxiomsCheck<'ctx> {
+    result: SatResult,
+    core: Vec<&'static str>,
+    model: Option<Model<'ctx>>,
+    reason: Option<String>,
+}
+
+impl AxiomsCheck<'_> {
+    fn unknown(reason: String) -> Self {
+        AxiomsCheck {
+            result: SatResult::Unknown,
+            core: Vec::new(),
+            model: None,
+            reason: Some(reason),
+        }
+    }
 }
 
 pub struct Proof<'ctx> {
     profile: Profile<'ctx>,
-    forall_conditions: Vec<Bool<'ctx>>,
-    exists_conditions: Vec<Bool<'ctx>>,
+    forall_conditions: Vec<(&'static str, Vec<Bool<'ctx>>)>,
+    exists_conditions: Vec<(&'static str, Vec<Bool<'ctx>>)>,
+    negated_formulas: Vec<(&'static str, Bool<'ctx>)>,
+    model: Option<Model<'ctx>>,
+    minimize: bool,
+    incremental: bool,
+    symmetry: bool,
+    deduplicate: bool,
+    condition_timeout: Option<u32>,
+    deadline: Option<Instant>,
+    checkpoint: Option<Checkpoint>,
     rule: Box<&'ctx dyn VotingRule<'ctx>>,
 }
 
 impl<'ctx> Proof<'ctx> {
     /// Creates a new proof with the given profile and rule.
     pub fn new(_m: usize, rule: &'ctx dyn VotingRule<'ctx>, profile: Profile<'ctx>) -> Self {
-        let forall_conditions: Vec<Bool<'ctx>> = Vec::new();
-        let exists_conditions: Vec<Bool<'ctx>> = Vec::new();
+        let forall_conditions = Vec::new();
+        let exists_conditions = Vec::new();
+        let negated_formulas = Vec::new();
         let rule = Box::new(rule);
         Proof {
             profile,
             forall_conditions,
             exists_conditions,
+            negated_formulas,
+            model: None,
+            minimize: false,
+            incremental: true,
+            symmetry: false,
+            deduplicate: false,
+            condition_timeout: None,
+            deadline: None,
+            checkpoint: None,
             rule,
         }
     }
@@ -42,19 +180,51 @@ impl<'ctx> Proof<'ctx> {
         let condition = A::condition(&self.profile, &self.rule);
         let ax_type = A::get_type();
         match ax_type {
-            AxiomType::Forall => self.forall_conditions.extend(condition),
-            AxiomType::Exists => self.exists_conditions.extend(condition),
+            AxiomType::Forall => self.forall_conditions.push((A::short_name(), condition)),
+            AxiomType::Exists => self.exists_conditions.push((A::short_name(), condition)),
         }
     }
 
+    /// Adds the negation of an axiom to the proof.
+    /// The negation is always asserted, such that the proof is unsatisfiable iff the other axioms imply the axiom.
+    pub fn add_negated_axiom<A: Axiom>(&mut self) {
+        let ctx = self.profile.get_ctx();
+        let vars = self.profile.all_vars();
+        let vars = vars.iter().map(|v| *v as &dyn Ast).collect::<Vec<_>>();
+        let preconditions = self.profile.preconditions();
+        let condition = A::condition(&self.profile, &self.rule);
+
+        let formula = match A::get_type() {
+            // Some profile violates one of the conditions.
+            AxiomType::Forall => {
+                let conditions = Bool::and(ctx, &condition.iter().collect::<Vec<_>>());
+                let violation = Bool::and(ctx, &[&preconditions, &conditions.not()]);
+                exists_const(ctx, &vars, &[], &violation)
+            }
+            // For some condition there is no witness.
+            AxiomType::Exists => {
+                let missing = condition
+                    .iter()
+                    .map(|formula| {
+                        let formula = preconditions.implies(&formula.not());
+                        forall_const(ctx, &vars, &[], &formula)
+                    })
+                    .collect::<Vec<_>>();
+                Bool::or(ctx, &missing.iter().collect::<Vec<_>>())
+            }
+        };
+        self.negated_formulas.push((A::short_name(), formula));
+    }
+
     /// Returns the number of votes per ranking given the model.
     fn get_profile(&self, model: &Model) -> Vec<String> {
-        self.profile
+        let mut result = self
+            .profile
             .votes
             .iter()
             .filter_map(|(ranking, var)| {
                 let ranking = ranking.to_string();
-                let val = model.get_const_interp(var);
+                let val = model.eval(var, true);
                 match val {
                     None => Some(format!("? -> {}", ranking)),
                     Some(val) => {
@@ -66,7 +236,317 @@ impl<'ctx> Proof<'ctx> {
                     }
                 }
             })
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>();
+
+        if let Some(candidate_votes) = &self.profile.candidate_votes {
+            for (candidate, choices) in candidate_votes {
+                let choice = choices.iter().find(|(_, var)| {
+                    model
+                        .eval(*var, true)
+                        .and_then(|val| val.as_i64())
+                        .is_some_and(|val| val > 0)
+                });
+                if let Some((ranking, _)) = choice {
+                    result.push(format!(
+                        "{} votes {}",
+                        candidate.to_string(),
+                        ranking.to_string()
+                    ));
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the winners of the profile given the model.
+    fn winners(&self, model: &Model) -> BTreeSet<Partition> {
+        self.winners_of(&self.profile, None, model)
+    }
+
+    /// Returns the winners of ```profile``` with ```extra_votes``` in the model.
+    fn winners_of(
+        &self,
+        profile: &Profile<'ctx>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'ctx>>>,
+        model: &Model,
+    ) -> BTreeSet<Partition> {
+        profile
+            .partitions
+            .iter()
+            .filter(|partition| {
+                let winner = self.rule.winner(partition, profile, extra_votes);
+                model
+                    .eval(&winner, true)
+                    .and_then(|val| val.as_bool())
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the other profiles condition ```index``` quantifies over, evaluated in the model.
+    fn quantified_outcomes<A: Axiom>(&self, index: usize, model: &Model) -> Vec<QuantifiedOutcome> {
+        let ctx = self.profile.get_ctx();
+        A::quantified_profiles(&self.profile, index)
+            .into_iter()
+            .map(|quantified| {
+                let (label, profile, winners) = match quantified {
+                    QuantifiedProfile::ExtraVoter(label, ranking) => {
+                        let votes = votes_of(&self.profile, model);
+                        let extra_votes =
+                            BTreeMap::from([(ranking.clone(), Int::from_i64(ctx, 1))]);
+                        let winners = self.winners_of(&self.profile, Some(&extra_votes), model);
+                        (label, concrete::with_vote(&votes, &ranking), winners)
+                    }
+                    QuantifiedProfile::Separate(label, profile) => {
+                        let winners = self.winners_of(&profile, None, model);
+                        (label, votes_of(&profile, model), winners)
+                    }
+                };
+                log::warn!("Profile {}:", label);
+                for (ranking, count) in profile.iter() {
+                    log::warn!("{} -> {}", count, ranking.to_string());
+                }
+                for winner in winners.iter() {
+                    log::warn!("Winner {}", winner.to_string());
+                }
+                QuantifiedOutcome {
+                    label,
+                    profile,
+                    winners,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the outcome of a condition given the model of its check.
+    fn condition_outcome(
+        &self,
+        index: usize,
+        held: Option<bool>,
+        model: Option<&Model>,
+    ) -> ConditionOutcome {
+        ConditionOutcome {
+            index,
+            held,
+            profile: model.map(|model| votes_of(&self.profile, model)),
+            winners: model.map(|model| self.winners(model)),
+            verified: None,
+            scores: None,
+            reason: None,
+            quantified: Vec::new(),
+        }
+    }
+
+    /// Returns the outcome of a condition the solver could not decide.
+    fn unknown_outcome(&self, index: usize, reason: String) -> ConditionOutcome {
+        log::warn!("Unknown for condition {}: {}", index, reason);
+        ConditionOutcome {
+            reason: Some(reason),
+            ..self.condition_outcome(index, None, None)
+        }
+    }
+
+    /// Replays the counterexample without the solver and checks that it violates the axiom.
+    fn verify_counterexample<A: Axiom>(&self, outcome: &mut ConditionOutcome) {
+        let Some(votes) = &outcome.profile else {
+            return;
+        };
+
+        let scores = self
+            .profile
+            .partitions
+            .iter()
+            .map(|partition| {
+                Some((
+                    partition.clone(),
+                    self.rule.concrete_score(partition, votes)?,
+                ))
+            })
+            .collect::<Option<BTreeMap<_, _>>>();
+        if let Some(scores) = &scores {
+            for (partition, score) in scores {
+                log::warn!("Score {}: {}", partition.to_string(), score);
+            }
+        }
+
+        let holds = A::holds_for_quantified(*self.rule, &self.profile, votes, &outcome.quantified);
+        match holds {
+            Some(false) => log::warn!("Verified: the counterexample violates {}", A::full_name()),
+            Some(true) => log::error!(
+                "The counterexample does not violate {} when evaluated concretely",
+                A::full_name()
+            ),
+            None => log::info!("The counterexample can not be evaluated concretely"),
+        }
+        outcome.verified = holds.map(|holds| !holds);
+        outcome.scores = scores;
+    }
+
+    /// Sets the exact number of voters of the profile, conditions generated afterwards use the new bound.
+    pub fn set_voters(&mut self, voters: Option<u64>) {
+        self.profile.voters = voters;
+    }
+
+    /// Sets the resource limits, the total timeout starts now.
+    /// The memory limit applies to every Z3 context of the process.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.condition_timeout = limits.condition_timeout;
+        self.deadline = limits
+            .total_timeout
+            .map(|timeout| Instant::now() + Duration::from_millis(timeout));
+        if let Some(memory) = limits.memory {
+            z3::set_global_param("memory_max_size", &memory.to_string());
+        }
+    }
+
+    /// Creates a solver respecting the timeouts.
+    /// Returns ```None```, if the total timeout is exceeded.
+    fn new_solver(&self) -> Option<Solver<'ctx>> {
+        let solver = Solver::new(self.profile.get_ctx());
+        self.set_timeout(&solver).then_some(solver)
+    }
+
+    /// Sets the timeout of the next check of ```solver```.
+    /// Returns false, if the total timeout is exceeded.
+    fn set_timeout(&self, solver: &Solver<'ctx>) -> bool {
+        let remaining = match self.deadline {
+            None => None,
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return false;
+                }
+                Some(remaining.as_millis().min(u32::MAX as u128) as u32)
+            }
+        };
+
+        let timeout = match (self.condition_timeout, remaining) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(timeout) = timeout {
+            let mut params = Params::new(self.profile.get_ctx());
+            params.set_u32("timeout", timeout);
+            solver.set_params(&params);
+        }
+        true
+    }
+
+    /// Sets whether the conditions of an axiom are checked with one solver using push and pop, the default.
+    /// Otherwise a new solver is created for every condition.
+    pub fn set_incremental(&mut self, incremental: bool) {
+        self.incremental = incremental;
+    }
+
+    /// Sets whether forall axioms are only checked on profiles which are the representative of their relabelings.
+    /// The result of the axiom is the same, however a condition may hold on the representatives only.
+    pub fn set_symmetry(&mut self, symmetry: bool) {
+        self.symmetry = symmetry;
+    }
+
+    /// Returns the preconditions of the counterexamples of ```A```, with symmetry breaking if it is sound.
+    fn counterexample_preconditions<A: Axiom>(&self) -> Bool<'ctx> {
+        let preconditions = self.profile.preconditions();
+        if !self.symmetry {
+            return preconditions;
+        }
+        if !A::is_neutral() || !self.rule.is_neutral() {
+            log::warn!(
+                "Symmetry breaking requires a neutral rule and axiom, checking all profiles"
+            );
+            return preconditions;
+        }
+        match self.profile.symmetry_breaking() {
+            Some(symmetry_breaking) => {
+                log::info!("Checking only representatives of relabeled profiles");
+                Bool::and(
+                    self.profile.get_ctx(),
+                    &[&preconditions, &symmetry_breaking],
+                )
+            }
+            None => {
+                log::warn!("Symmetry breaking requires rankings closed under relabeling and no candidate voters, checking all profiles");
+                preconditions
+            }
+        }
+    }
+
+    /// Sets whether conditions relabeling an earlier condition of the axiom are skipped when checking iteratively.
+    /// Skipped conditions have no outcome.
+    pub fn set_deduplicate(&mut self, deduplicate: bool) {
+        self.deduplicate = deduplicate;
+    }
+
+    /// Returns for every condition of ```A``` whether it is checked, ```None``` if every condition is checked.
+    /// Only the first condition of every canonical key is checked, independent of the shard.
+    fn representatives<A: Axiom>(&self) -> Option<Vec<bool>> {
+        if !self.deduplicate {
+            return None;
+        }
+        if !A::is_neutral() || !self.rule.is_neutral() {
+            log::warn!("Skipping relabeled conditions requires a neutral rule and axiom, checking all conditions");
+            return None;
+        }
+        if !self.profile.closed_under_relabeling() {
+            log::warn!("Skipping relabeled conditions requires the rankings to be closed under relabeling, checking all conditions");
+            return None;
+        }
+        // The representative of a skipped condition may only be checked on other profiles.
+        if self.symmetry && matches!(A::get_type(), AxiomType::Forall) {
+            log::warn!("Skipping relabeled conditions is not sound with symmetry breaking, checking all conditions");
+            return None;
+        }
+        let keys = A::canonical_keys(&self.profile)?;
+        let mut seen = BTreeSet::new();
+        Some(keys.into_iter().map(|key| seen.insert(key)).collect())
+    }
+
+    /// Returns the solver for the next condition with the preconditions asserted and a new scope pushed.
+    /// The solver is reused if the proof is incremental.
+    /// Returns ```None```, if the total timeout is exceeded.
+    fn condition_solver<'s>(
+        &self,
+        solver: &'s mut Option<Solver<'ctx>>,
+        preconditions: &Bool<'ctx>,
+    ) -> Option<&'s Solver<'ctx>> {
+        if !self.incremental || solver.is_none() {
+            let base = Solver::new(self.profile.get_ctx());
+            base.assert(preconditions);
+            *solver = Some(base);
+        }
+        let solver = solver.as_ref()?;
+        if !self.set_timeout(solver) {
+            return None;
+        }
+        solver.push();
+        Some(solver)
+    }
+
+    /// Sets the checkpoint storing the conditions which held, these are skipped when checking iteratively.
+    pub fn set_checkpoint(&mut self, checkpoint: Option<Checkpoint>) {
+        self.checkpoint = checkpoint;
+    }
+
+    /// Sets whether counterexamples are minimized before they are reported.
+    pub fn set_minimize(&mut self, minimize: bool) {
+        self.minimize = minimize;
+    }
+
+    /// Returns a model of ```formula``` with as few voters as possible.
+    /// Every check respects the timeouts, ```None``` is returned if no model is found in time.
+    fn minimized_model(&self, formula: &Bool<'ctx>) -> Option<Model<'ctx>> {
+        log::info!("Minimizing counterexample");
+        let limit = |solver: &Solver<'ctx>| self.set_timeout(solver);
+        let model = minimize(&self.profile, formula, &limit)?;
+        let votes = votes_of(&self.profile, &model);
+        let (_, shrunk) = shrink(&self.profile, formula, votes, &limit);
+        shrunk.or(Some(model))
+    }
+
+    /// Returns the model of the last check, if the check was satisfiable.
+    pub fn model(&self) -> Option<&Model<'ctx>> {
+        self.model.as_ref()
     }
 
     /// Prints the profile given the model.
@@ -78,117 +558,516 @@ impl<'ctx> Proof<'ctx> {
     }
 
     /// Searches for a witness that satisfies the axiom.
-    fn check_iteratively_exists_axiom<A: Axiom>(&mut self) -> SatResult {
+    fn check_iteratively_exists_axiom<A: Axiom>(&mut self, shard: Option<&Shard>) -> AxiomOutcome {
         log::info!("Start checking Axiom {}", A::full_name());
-        let ctx = self.profile.get_ctx();
-        let non_negative = self.profile.vars_nonnegative();
-        let sum_positive = self.profile.vars_sum_positive();
+        let preconditions = self.profile.preconditions();
+        let mut conditions = Vec::new();
+        let representatives = self.representatives::<A>();
+        let mut skipped = 0;
 
-        for condition in A::condition_generator(&self.profile, &self.rule) {
-            let solver = Solver::new(ctx);
+        let mut axiom_result = SatResult::Sat;
+        let mut current_solver = None;
+        let start = Instant::now();
+
+        for (index, condition) in A::lazy_condition_generator(&self.profile, &self.rule).enumerate()
+        {
+            if let Some(shard) = shard {
+                if !shard.owns(index) {
+                    continue;
+                }
+                if shard.is_obsolete(index) {
+                    break;
+                }
+            }
+            if representatives
+                .as_ref()
+                .is_some_and(|x| x.get(index) == Some(&false))
+            {
+                skipped += 1;
+                continue;
+            }
+            if self
+                .checkpoint
+                .as_ref()
+                .is_some_and(|x| x.is_completed(index))
+            {
+                conditions.push(self.condition_outcome(index, Some(true), None));
+                continue;
+            }
+            let condition = condition();
+            let Some(solver) = self.condition_solver(&mut current_solver, &preconditions) else {
+                conditions.push(self.unknown_outcome(index, "total timeout".to_string()));
+                return self.axiom_outcome::<A>(SatResult::Unknown, conditions);
+            };
             solver.assert(&condition);
-            solver.assert(&non_negative);
-            solver.assert(&sum_positive);
             let result = solver.check();
             match result {
                 SatResult::Sat => {
                     log::info!("Sat with witness:");
                     let model = solver.get_model();
-                    match model {
+                    match &model {
                         None => log::info!("Failed to get Model"),
-                        Some(model) => self.print_profile(&model),
+                        Some(model) => self.print_profile(model),
+                    }
+                    conditions.push(self.condition_outcome(index, Some(true), model.as_ref()));
+                    if let Some(checkpoint) = &mut self.checkpoint {
+                        checkpoint.record(index);
                     }
                 }
                 SatResult::Unknown => {
-                    log::warn!("Unknown");
-                    return SatResult::Unknown;
+                    let reason = solver.get_reason_unknown().unwrap_or_default();
+                    conditions.push(self.unknown_outcome(index, reason));
+                    axiom_result = SatResult::Unknown;
                 }
                 SatResult::Unsat => {
                     log::warn!("Unsat - Could not find a witness");
-                    return SatResult::Unsat;
+                    if let Some(shard) = shard {
+                        shard.report_failure(index);
+                    }
+                    conditions.push(self.condition_outcome(index, Some(false), None));
+                    return self.axiom_outcome::<A>(SatResult::Unsat, conditions);
                 }
             }
+            solver.pop(1);
+        }
+        log::info!(
+            "Checked {} conditions in {:?}",
+            conditions.len(),
+            start.elapsed()
+        );
+        if skipped > 0 {
+            log::info!("Skipped {} conditions relabeling checked ones", skipped);
         }
-        SatResult::Sat
+        self.axiom_outcome::<A>(axiom_result, conditions)
     }
 
     /// Searches for a counterexample that falsifies the axiom.
-    fn check_iteratively_forall_axiom<A: Axiom>(&mut self) -> SatResult {
+    fn check_iteratively_forall_axiom<A: Axiom>(&mut self, shard: Option<&Shard>) -> AxiomOutcome {
         log::warn!("Start checking Axiom {}", A::full_name());
         let ctx = self.profile.get_ctx();
-        let non_negative = self.profile.vars_nonnegative();
-        let sum_positive = self.profile.vars_sum_positive();
+        let preconditions = self.counterexample_preconditions::<A>();
+        let mut conditions = Vec::new();
+        let representatives = self.representatives::<A>();
+        let mut skipped = 0;
 
-        for condition in A::condition_generator(&self.profile, &self.rule) {
-            let solver = Solver::new(ctx);
+        let mut axiom_result = SatResult::Sat;
+        let mut current_solver = None;
+        let start = Instant::now();
+
+        for (index, condition) in A::lazy_condition_generator(&self.profile, &self.rule).enumerate()
+        {
+            if let Some(shard) = shard {
+                if !shard.owns(index) {
+                    continue;
+                }
+                if shard.is_obsolete(index) {
+                    break;
+                }
+            }
+            if representatives
+                .as_ref()
+                .is_some_and(|x| x.get(index) == Some(&false))
+            {
+                skipped += 1;
+                continue;
+            }
+            if self
+                .checkpoint
+                .as_ref()
+                .is_some_and(|x| x.is_completed(index))
+            {
+                conditions.push(self.condition_outcome(index, Some(true), None));
+                continue;
+            }
+            let condition = condition();
+            let Some(solver) = self.condition_solver(&mut current_solver, &preconditions) else {
+                conditions.push(self.unknown_outcome(index, "total timeout".to_string()));
+                return self.axiom_outcome::<A>(SatResult::Unknown, conditions);
+            };
             solver.assert(&condition.not());
-            solver.assert(&non_negative);
-            solver.assert(&sum_positive);
             let result = solver.check();
             match result {
                 SatResult::Sat => {
                     log::warn!("Unsat, Counterexample:");
-                    let model = solver.get_model();
-                    match model {
+                    if let Some(shard) = shard {
+                        shard.report_failure(index);
+                    }
+                    let mut model = solver.get_model();
+                    if self.minimize {
+                        let formula = Bool::and(ctx, &[&condition.not(), &preconditions]);
+                        model = self.minimized_model(&formula).or(model);
+                    }
+                    match &model {
                         None => log::error!("Failed to get Model"),
-                        Some(model) => self.print_profile(&model),
+                        Some(model) => self.print_profile(model),
+                    }
+                    let mut outcome = self.condition_outcome(index, Some(false), model.as_ref());
+                    if let Some(model) = &model {
+                        outcome.quantified = self.quantified_outcomes::<A>(index, model);
+                    }
+                    self.verify_counterexample::<A>(&mut outcome);
+                    conditions.push(outcome);
+                    self.model = model;
+                    return self.axiom_outcome::<A>(SatResult::Unsat, conditions);
+                }
+                SatResult::Unknown => {
+                    let reason = solver.get_reason_unknown().unwrap_or_default();
+                    conditions.push(self.unknown_outcome(index, reason));
+                    axiom_result = SatResult::Unknown;
+                }
+                _ => {
+                    conditions.push(self.condition_outcome(index, Some(true), None));
+                    if let Some(checkpoint) = &mut self.checkpoint {
+                        checkpoint.record(index);
                     }
-                    return SatResult::Unsat;
                 }
-                SatResult::Unknown => return SatResult::Unknown,
-                _ => {}
             }
+            solver.pop(1);
+        }
+        log::info!(
+            "Checked {} conditions in {:?}",
+            conditions.len(),
+            start.elapsed()
+        );
+        if skipped > 0 {
+            log::info!("Skipped {} conditions relabeling checked ones", skipped);
+        }
+        self.axiom_outcome::<A>(axiom_result, conditions)
+    }
+
+    fn axiom_outcome<A: Axiom>(
+        &self,
+        result: SatResult,
+        conditions: Vec<ConditionOutcome>,
+    ) -> AxiomOutcome {
+        AxiomOutcome {
+            name: A::short_name(),
+            result,
+            conditions,
         }
-        SatResult::Sat
     }
 
     /// Checks if the profile satisfies this axiom. An early termination is possible if the axiom is not satisfied.
-    pub fn check_iteratively<A: Axiom>(&mut self) -> SatResult {
+    /// The checkpoint is removed once the axiom is decided.
+    pub fn check_iteratively<A: Axiom>(&mut self) -> AxiomOutcome {
         let axiom_type = A::get_type();
-        match axiom_type {
-            AxiomType::Forall => self.check_iteratively_forall_axiom::<A>(),
-            AxiomType::Exists => self.check_iteratively_exists_axiom::<A>(),
+        let outcome = match axiom_type {
+            AxiomType::Forall => self.check_iteratively_forall_axiom::<A>(None),
+            AxiomType::Exists => self.check_iteratively_exists_axiom::<A>(None),
+        };
+        if outcome.result != SatResult::Unknown {
+            if let Some(checkpoint) = self.checkpoint.take() {
+                checkpoint.remove();
+            }
         }
+        outcome
     }
 
-    /// Checks if the profile satisfies the axiom previously added.
-    pub fn check(&mut self) -> (SatResult, Option<Model>) {
-        let ctx = self.profile.get_ctx();
-        let solver = Solver::new(ctx);
-        let all_forall_conditions = self.forall_conditions.iter().collect::<Vec<_>>();
-        let all_forall_conditions: Bool<'_> = Bool::and(ctx, &all_forall_conditions);
+    /// Checks the axiom on every profile up to the bound on the voters without the solver.
+    /// The profiles are enumerated by their number of voters, therefore a counterexample has as few voters as possible.
+    pub fn brute_force<A: Axiom>(&mut self) -> AxiomOutcome {
+        log::warn!("Start enumerating profiles for Axiom {}", A::full_name());
+        let Some(max_voters) = self.profile.voters.or(self.profile.max_voters) else {
+            let outcome = self.unknown_outcome(0, "the number of voters is unbounded".to_string());
+            return self.axiom_outcome::<A>(SatResult::Unknown, vec![outcome]);
+        };
+        if self.profile.candidate_votes.is_some() {
+            let outcome =
+                self.unknown_outcome(0, "candidates as voters are not supported".to_string());
+            return self.axiom_outcome::<A>(SatResult::Unknown, vec![outcome]);
+        }
 
+        let rankings = self.profile.votes.keys().cloned().collect::<Vec<_>>();
+        let mut index = 0;
+        for voters in self.profile.voters.unwrap_or(1)..=max_voters {
+            for votes in concrete::profiles(&rankings, voters) {
+                match A::holds_concretely(*self.rule, &self.profile, &votes) {
+                    Some(true) => index += 1,
+                    Some(false) => {
+                        log::warn!("Counterexample with {} voters:", voters);
+                        for (ranking, count) in votes.iter() {
+                            log::warn!("{} -> {}", count, ranking.to_string());
+                        }
+                        let mut outcome = ConditionOutcome {
+                            winners: self.rule.concrete_winners(&votes),
+                            profile: Some(votes),
+                            ..self.condition_outcome(index, Some(false), None)
+                        };
+                        self.verify_counterexample::<A>(&mut outcome);
+                        return self.axiom_outcome::<A>(SatResult::Unsat, vec![outcome]);
+                    }
+                    None => {
+                        let reason = format!("{} can not be evaluated concretely", A::full_name());
+                        let outcome = self.unknown_outcome(index, reason);
+                        return self.axiom_outcome::<A>(SatResult::Unknown, vec![outcome]);
+                    }
+                }
+            }
+        }
+        log::warn!("No counterexample among {} profiles", index);
+        self.axiom_outcome::<A>(SatResult::Sat, Vec::new())
+    }
+
+    /// Checks the axiom with 1, 2, ... voters up to the bound on the voters until it is violated.
+    /// Returns the last outcome and the number of voters of the first violation.
+    /// The search stops at the first undecided check, a violation with more voters may not be the smallest then.
+    pub fn smallest_electorate<A: Axiom>(&mut self) -> (AxiomOutcome, Option<u64>) {
+        let Some(max_voters) = self.profile.max_voters else {
+            let outcome = self.unknown_outcome(0, "the number of voters is unbounded".to_string());
+            return (
+                self.axiom_outcome::<A>(SatResult::Unknown, vec![outcome]),
+                None,
+            );
+        };
+        let mut outcome = None;
+        for voters in 1..=max_voters {
+            log::warn!("Checking with {} voters", voters);
+            self.set_voters(Some(voters));
+            let result = self.check_iteratively::<A>();
+            match result.result {
+                SatResult::Sat => outcome = Some(result),
+                SatResult::Unsat => return (result, Some(voters)),
+                SatResult::Unknown => {
+                    log::warn!(
+                        "Undecided with {} voters, the smallest electorate is inconclusive",
+                        voters
+                    );
+                    return (result, None);
+                }
+            }
+        }
+        (outcome.unwrap(), None)
+    }
+
+    /// Checks the conditions of the axiom belonging to the shard of a worker.
+    pub fn check_shard<A: Axiom>(&mut self, shard: &Shard) -> AxiomOutcome {
+        match A::get_type() {
+            AxiomType::Forall => self.check_iteratively_forall_axiom::<A>(Some(shard)),
+            AxiomType::Exists => self.check_iteratively_exists_axiom::<A>(Some(shard)),
+        }
+    }
+
+    /// Returns the names of the axiom previously added.
+    pub fn axiom_names(&self) -> Vec<&'static str> {
+        self.forall_conditions
+            .iter()
+            .chain(self.exists_conditions.iter())
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Returns the closed formula of every axiom previously added.
+    /// The forall axioms must hold for every profile, for the exists axioms every condition must hold for some profile.
+    fn axiom_formulas(&self) -> Vec<(&'static str, Bool<'ctx>)> {
+        let ctx = self.profile.get_ctx();
         let vars = self
             .profile
             .all_vars()
             .into_iter()
             .map(|v| v as &dyn Ast)
             .collect::<Vec<_>>();
+        let preconditions = self.profile.preconditions();
+
+        let forall_formulas = self.forall_conditions.iter().map(|(name, conditions)| {
+            let conditions = Bool::and(ctx, &conditions.iter().collect::<Vec<_>>());
+            let formula = preconditions.implies(&conditions);
+            (*name, forall_const(ctx, &vars, &[], &formula))
+        });
 
-        let non_negative = self.profile.vars_nonnegative();
-        let sum_positive = self.profile.vars_sum_positive();
-        let preconditions = Bool::and(ctx, &[&non_negative, &sum_positive]);
-        let formula = preconditions.implies(&all_forall_conditions);
+        let exists_formulas = self.exists_conditions.iter().map(|(name, conditions)| {
+            let witnesses = conditions
+                .iter()
+                .map(|formula| {
+                    let formula_with_assumptions = Bool::and(ctx, &[&preconditions, formula]);
+                    exists_const(ctx, &vars, &[], &formula_with_assumptions)
+                })
+                .collect::<Vec<_>>();
+            (*name, Bool::and(ctx, &witnesses.iter().collect::<Vec<_>>()))
+        });
 
-        let forall_quantor = forall_const(ctx, &vars, &[], &formula);
+        forall_formulas.chain(exists_formulas).collect()
+    }
 
-        let exists_formulas = self
-            .exists_conditions
-            .iter()
-            .map(|formula| {
-                let formula_with_assumptions =
-                    Bool::and(ctx, &[&non_negative, &sum_positive, formula]);
-                exists_const(ctx, &vars, &[], &formula_with_assumptions)
+    /// Returns the formula asserted by ```check``` as a standalone SMT-LIB2 problem.
+    /// The axioms previously added hold together iff the problem is satisfiable.
+    pub fn smt2(&self) -> String {
+        let solver = Solver::new(self.profile.get_ctx());
+        for (_, formula) in &self.negated_formulas {
+            solver.assert(formula);
+        }
+        for (_, formula) in self.axiom_formulas() {
+            solver.assert(&formula);
+        }
+        format!(
+            "; The axioms {} hold together iff this problem is sat\n{}(check-sat)\n",
+            self.axiom_names().join(", "),
+            solver
+        )
+    }
+
+    /// Returns every condition of the axiom as a standalone SMT-LIB2 problem, in the order they are checked iteratively.
+    pub fn condition_smt2<A: Axiom>(&self) -> Vec<String> {
+        let ctx = self.profile.get_ctx();
+        let preconditions = self.profile.preconditions();
+        let axiom_type = A::get_type();
+        let holds_if = match axiom_type {
+            AxiomType::Forall => "unsat",
+            AxiomType::Exists => "sat",
+        };
+        A::condition_generator(&self.profile, &self.rule)
+            .enumerate()
+            .map(|(index, condition)| {
+                let solver = Solver::new(ctx);
+                solver.assert(&preconditions);
+                match axiom_type {
+                    AxiomType::Forall => solver.assert(&condition.not()),
+                    AxiomType::Exists => solver.assert(&condition),
+                }
+                format!(
+                    "; Condition {} of {} holds iff this problem is {}\n{}(check-sat)\n",
+                    index,
+                    A::short_name(),
+                    holds_if,
+                    solver
+                )
             })
+            .collect()
+    }
+
+    /// Checks the axioms ```axioms``` of the axioms previously added.
+    /// Every axiom is tracked by an assumption, if the axioms are unsatisfiable the unsat core is returned.
+    fn check_axioms(&self, axioms: &[&str]) -> AxiomsCheck<'ctx> {
+        let ctx = self.profile.get_ctx();
+        let Some(solver) = self.new_solver() else {
+            return AxiomsCheck::unknown("total timeout".to_string());
+        };
+
+        for (name, formula) in &self.negated_formulas {
+            log::info!("Asserting negation of {}", name);
+            solver.assert(formula);
+        }
+
+        let mut assumptions = Vec::new();
+        for (i, (name, formula)) in self.axiom_formulas().into_iter().enumerate() {
+            if !axioms.contains(&name) {
+                continue;
+            }
+            let assumption = Bool::new_const(ctx, format!("axiom_{}_{}", i, name));
+            solver.assert(&assumption.implies(&formula));
+            assumptions.push((name, assumption));
+        }
+
+        let literals = assumptions
+            .iter()
+            .map(|(_, assumption)| assumption.clone())
             .collect::<Vec<_>>();
+        let result = solver.check_assumptions(&literals);
+
+        let core = if result == SatResult::Unsat {
+            let core = solver.get_unsat_core();
+            assumptions
+                .iter()
+                .filter(|(_, assumption)| core.contains(assumption))
+                .map(|(name, _)| *name)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if result == SatResult::Unknown {
+            let reason = solver.get_reason_unknown().unwrap_or_default();
+            return AxiomsCheck::unknown(reason);
+        }
+        AxiomsCheck {
+            result,
+            core,
+            model: solver.get_model(),
+            reason: None,
+        }
+    }
+
+    /// Checks if the axioms ```axioms``` of the axioms previously added are satisfiable together.
+    pub fn check_subset(&self, axioms: &[&str]) -> SatResult {
+        self.check_axioms(axioms).result
+    }
+
+    /// Returns the axioms in the unsat core, if the axioms previously added are unsatisfiable.
+    pub fn unsat_core(&self) -> Option<Vec<&'static str>> {
+        let check = self.check_axioms(&self.axiom_names());
+        match check.result {
+            SatResult::Unsat => Some(check.core),
+            _ => None,
+        }
+    }
 
-        solver.assert(&forall_quantor);
-        for exist_formula in exists_formulas {
-            solver.assert(&exist_formula);
+    /// Shrinks ```core```, the unsat core returned by ```check```, to a minimal unsatisfiable subset of the axioms.
+    /// Removing any axiom of the subset makes the remaining axioms satisfiable.
+    /// If removing an axiom could not be decided, the axiom is kept and the subset is not proven minimal.
+    pub fn minimal_unsat_subset(&self, core: &[&'static str]) -> UnsatSubset {
+        let mut subset = core.to_vec();
+        let mut minimal = true;
+
+        let mut i = 0;
+        while i < subset.len() {
+            let mut candidate = subset.clone();
+            let removed = candidate.remove(i);
+            log::info!("Trying to remove axiom {}", removed);
+            let check = self.check_axioms(&candidate);
+            match check.result {
+                SatResult::Unsat => subset = check.core,
+                SatResult::Sat => i += 1,
+                SatResult::Unknown => {
+                    log::warn!(
+                        "Could not decide whether axiom {} is required: {}",
+                        removed,
+                        check.reason.unwrap_or_default()
+                    );
+                    minimal = false;
+                    i += 1;
+                }
+            }
         }
+        UnsatSubset {
+            axioms: subset,
+            minimal,
+        }
+    }
+
+    /// Checks if the profile satisfies the axiom previously added.
+    /// If the axioms are unsatisfiable, the axioms of the unsat core are logged.
+    /// The model of a satisfiable check is available through ```model```.
+    pub fn check(&mut self) -> ProofResult {
         log::info!("Start Checking");
-        let result = solver.check();
+        let AxiomsCheck {
+            result,
+            core,
+            model,
+            reason,
+        } = self.check_axioms(&self.axiom_names());
+        if result == SatResult::Unsat {
+            log::warn!("Unsat core: {}", core.join(", "));
+        }
+        if let Some(reason) = &reason {
+            log::warn!("Unknown: {}", reason);
+        }
+        self.model = model;
 
-        (result, solver.get_model())
+        let axioms = self
+            .axiom_names()
+            .into_iter()
+            .map(|name| AxiomOutcome {
+                name,
+                result: match result {
+                    SatResult::Unsat if !core.contains(&name) => SatResult::Unknown,
+                    result => result,
+                },
+                conditions: Vec::new(),
+            })
+            .collect();
+        ProofResult {
+            result,
+            axioms,
+            unsat_core: core,
+            reason,
+        }
     }
 }
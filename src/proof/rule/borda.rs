@@ -1,3 +1,4 @@
+use crate::proof::concrete::Votes;
 use crate::structures::Ranking;
 use crate::structures::Structure;
 use crate::{proof::profile::Profile, structures::Partition};
@@ -9,15 +10,16 @@ use std::{
 use z3::ast::Int;
 use z3::ast::Real;
 
-use super::rule_trait::VotingRule;
+use super::rule_trait::{RuleConstructor, RuleId, VotingRule};
 
 pub struct Borda {
     pub partitions: BTreeSet<Partition>,
     pub alternatives: usize,
     pub num_candidates: usize,
+    id: RuleId,
 }
 
-impl<'a> VotingRule<'a> for Borda {
+impl RuleConstructor for Borda {
     fn new(m: usize) -> Self {
         let partitions = Partition::all(m);
         let alternatives = partitions.len();
@@ -27,9 +29,12 @@ impl<'a> VotingRule<'a> for Borda {
             partitions,
             alternatives,
             num_candidates,
+            id: RuleId::next(),
         }
     }
+}
 
+impl<'a> VotingRule<'a> for Borda {
     fn score(
         &self,
         partition: &Partition,
@@ -75,10 +80,30 @@ impl<'a> VotingRule<'a> for Borda {
         total_score.to_real()
     }
 
+    fn concrete_score(&self, partition: &Partition, votes: &Votes) -> Option<f64> {
+        let score = votes
+            .iter()
+            .map(|(ranking, count)| {
+                let class_index = ranking.index(partition);
+                let num_alternatives_before = ranking
+                    .order_type()
+                    .into_iter()
+                    .take(class_index)
+                    .sum::<usize>();
+                count * (self.alternatives - 1 - num_alternatives_before) as u64
+            })
+            .sum::<u64>();
+        Some(score as f64)
+    }
+
     fn all_partitions(&self) -> BTreeSet<Partition> {
         self.partitions.clone()
     }
 
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
     fn name() -> &'static str
     where
         Self: Sized,
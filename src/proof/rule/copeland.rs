@@ -1,4 +1,5 @@
-use super::rule_trait::VotingRule;
+use super::rule_trait::{RuleConstructor, RuleId, VotingRule};
+use crate::proof::concrete::{pairwise_majority, Votes};
 use crate::structures::Ranking;
 use crate::structures::Structure;
 use crate::{proof::profile::Profile, structures::Partition};
@@ -12,12 +13,10 @@ pub struct Copeland {
     pub partitions: BTreeSet<Partition>,
     pub alternatives: usize,
     pub num_candidates: usize,
+    id: RuleId,
 }
 
-impl<'a> VotingRule<'a> for Copeland
-where
-    Self: Sized,
-{
+impl RuleConstructor for Copeland {
     fn new(m: usize) -> Self {
         let partitions = Partition::all(m);
         let alternatives = partitions.len();
@@ -27,9 +26,15 @@ where
             partitions,
             alternatives,
             num_candidates,
+            id: RuleId::next(),
         }
     }
+}
 
+impl<'a> VotingRule<'a> for Copeland
+where
+    Self: Sized,
+{
     fn score(
         &self,
         partition: &Partition,
@@ -42,38 +47,8 @@ where
             .partitions
             .iter()
             .map(|other| {
-                let mut pro_variables = Vec::new();
-                let mut con_variables = Vec::new();
-                profile.votes.iter().for_each(|(ranking, var)| {
-                    if ranking.is_strictly_preferred(partition, other) {
-                        pro_variables.push(var);
-
-                        if let Some(extra_votes) = extra_vote {
-                            if let Some(extra_vote) = extra_votes.get(ranking) {
-                                pro_variables.push(extra_vote);
-                            }
-                        }
-                    } else if ranking.is_strictly_preferred(other, partition) {
-                        con_variables.push(var);
-                        if let Some(extra_votes) = extra_vote {
-                            if let Some(extra_vote) = extra_votes.get(ranking) {
-                                con_variables.push(extra_vote);
-                            }
-                        }
-                    }
-                });
-
-                let pro_sum = if pro_variables.is_empty() {
-                    Int::from_i64(ctx, 0)
-                } else {
-                    Int::add(ctx, &pro_variables)
-                };
-
-                let con_sum = if con_variables.is_empty() {
-                    Int::from_i64(ctx, 0)
-                } else {
-                    Int::add(ctx, &con_variables)
-                };
+                let pro_sum = profile.pairwise_support(partition, other, extra_vote);
+                let con_sum = profile.pairwise_support(other, partition, extra_vote);
 
                 let score = pro_sum.sub(&con_sum);
                 let maj_win = score.gt(&Int::from_i64(ctx, 0));
@@ -84,10 +59,23 @@ where
         Int::add(ctx, &scores).to_real()
     }
 
+    fn concrete_score(&self, partition: &Partition, votes: &Votes) -> Option<f64> {
+        let score = self
+            .partitions
+            .iter()
+            .filter(|other| pairwise_majority(votes, partition, other))
+            .count();
+        Some(score as f64)
+    }
+
     fn all_partitions(&self) -> BTreeSet<Partition> {
         self.partitions.clone()
     }
 
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
     fn name() -> &'static str
     where
         Self: Sized,
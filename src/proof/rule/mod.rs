@@ -2,11 +2,13 @@ mod borda;
 mod copeland;
 mod rule_trait;
 mod scoring;
+mod tiebreaking;
 // mod new_rule;
 pub use borda::Borda;
 pub use copeland::Copeland;
-pub use rule_trait::VotingRule;
+pub use rule_trait::{RuleConstructor, RuleId, ScoreTable, VotingRule};
 pub use scoring::Scoring;
+pub use tiebreaking::TieBreaking;
 // pub use new_rule::MyRule;
 
 macro_rules! get_rule {
@@ -36,7 +38,7 @@ macro_rules! get_rule {
         }
 
 
-        pub fn get_rule<'a :'b, 'b>(m: usize, rule: &'b str) -> Box<dyn VotingRule<'a> + 'b> {
+        pub fn get_rule<'a>(m: usize, rule: &str) -> Box<dyn VotingRule<'a>> {
             let lowercase = rule.to_ascii_lowercase();
             let name = lowercase.as_str();
             let obj: Box<dyn VotingRule>;
@@ -9,15 +9,16 @@ use std::{
 use z3::ast::Int;
 use z3::ast::Real;
 
-use super::rule_trait::VotingRule;
+use super::rule_trait::{RuleConstructor, RuleId, VotingRule};
 
 pub struct MyRule {
     pub partitions: BTreeSet<Partition>,
     pub alternatives: usize,
     pub num_candidates: usize,
+    id: RuleId,
 }
 
-impl<'a> VotingRule<'a> for MyRule {
+impl RuleConstructor for MyRule {
     fn new(m: usize) -> Self {
         let partitions = Partition::all(m);
         let alternatives = partitions.len();
@@ -27,9 +28,12 @@ impl<'a> VotingRule<'a> for MyRule {
             partitions,
             alternatives,
             num_candidates,
+            id: RuleId::next(),
         }
     }
+}
 
+impl<'a> VotingRule<'a> for MyRule {
     fn score(
         &self,
         partition: &Partition,
@@ -43,6 +47,10 @@ impl<'a> VotingRule<'a> for MyRule {
         self.partitions.clone()
     }
 
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
     fn name() -> &'static str
     where
         Self: Sized,
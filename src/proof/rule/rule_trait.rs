@@ -1,27 +1,55 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use z3::ast::{Ast, Real};
 use z3::ast::{Bool, Int};
 
 use crate::{
-    proof::profile::Profile,
+    proof::{concrete::Votes, profile::Profile},
     structures::{Partition, Ranking},
 };
 
-pub trait VotingRule<'a> {
+/// The scores of all partitions for one profile and extra votes.
+pub type ScoreTable<'a> = BTreeMap<Partition, Real<'a>>;
+
+/// Identifies a rule instance in the score table caches of the profiles.
+/// Unlike the address of the rule, an identifier is never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RuleId(u64);
+
+impl RuleId {
+    /// Returns a new identifier, different from all identifiers returned before.
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        RuleId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Rules which are determined by the number of candidates alone, such that they can be selected by name.
+/// Rules with further parameters, like ```TieBreaking``` and ```Scoring```, have their own constructors.
+pub trait RuleConstructor {
     /// Creates a new instance of the rule.
-    fn new(m: usize) -> Self
-    where
-        Self: Sized;
+    fn new(m: usize) -> Self;
+}
 
+pub trait VotingRule<'a> {
     /// Returns the name of the rule.
     fn name() -> &'static str
     where
         Self: Sized;
 
+    /// Returns true, if relabeling the candidates of a profile relabels its winners in the same way.
+    fn is_neutral(&self) -> bool {
+        true
+    }
+
     /// Returns all possible partitions.
     fn all_partitions(&self) -> BTreeSet<Partition>;
 
+    /// Returns the identifier of this rule instance.
+    fn id(&self) -> RuleId;
+
     /// Returns the score of ```partition``` in the profile.
     fn score(
         &self,
@@ -30,6 +58,52 @@ pub trait VotingRule<'a> {
         extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
     ) -> Real<'a>;
 
+    /// Returns the scores of all partitions.
+    /// The table is built once per profile, rule and extra votes and reused afterwards.
+    fn score_table(
+        &self,
+        profile: &Profile<'a>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Rc<ScoreTable<'a>> {
+        profile.score_table(self.id(), extra_votes, || {
+            self.all_partitions()
+                .into_iter()
+                .map(|partition| {
+                    let score = self.score(&partition, profile, extra_votes);
+                    (partition, score)
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the score of ```partition``` for concrete votes.
+    /// Returns ```None```, if the rule can not be evaluated without the solver.
+    fn concrete_score(&self, _partition: &Partition, _votes: &Votes) -> Option<f64> {
+        None
+    }
+
+    /// Returns the winners for concrete votes, these are the partitions with the highest score.
+    fn concrete_winners(&self, votes: &Votes) -> Option<BTreeSet<Partition>> {
+        let scores = self
+            .all_partitions()
+            .into_iter()
+            .map(|partition| {
+                let score = self.concrete_score(&partition, votes)?;
+                Some((partition, score))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let max = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let winners = scores
+            .into_iter()
+            .filter(|(_, score)| *score == max)
+            .map(|(partition, _)| partition)
+            .collect();
+        Some(winners)
+    }
+
     /// Returns the condition for ```partition``` to be the only winner.
     fn only_winner(
         &self,
@@ -38,16 +112,15 @@ pub trait VotingRule<'a> {
         extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
     ) -> Bool<'a> {
         let ctx = profile.get_ctx();
-        let score = self.score(partition, profile, extra_votes);
-        let scores = self
-            .all_partitions()
-            .into_iter()
-            .filter_map(|x| {
-                if &x == partition {
+        let table = self.score_table(profile, extra_votes);
+        let score = &table[partition];
+        let scores = table
+            .iter()
+            .filter_map(|(x, other_score)| {
+                if x == partition {
                     None
                 } else {
-                    let other_score = self.score(&x, profile, extra_votes);
-                    let condition = score.gt(&other_score);
+                    let condition = score.gt(other_score);
                     Some(condition)
                 }
             })
@@ -76,15 +149,11 @@ pub trait VotingRule<'a> {
         extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
     ) -> Bool<'a> {
         let ctx = profile.get_ctx();
-        let score = self.score(partition, profile, extra_votes);
-        let scores: Vec<Bool<'a>> = self
-            .all_partitions()
-            .into_iter()
-            .map(|x| {
-                let other_score = self.score(&x, profile, extra_votes);
-                let condition = score.ge(&other_score);
-                condition
-            })
+        let table = self.score_table(profile, extra_votes);
+        let score = &table[partition];
+        let scores: Vec<Bool<'a>> = table
+            .values()
+            .map(|other_score| score.ge(other_score))
             .collect::<Vec<_>>();
 
         let scores_vec: Vec<&Bool<'a>> = scores.iter().collect();
@@ -153,10 +222,8 @@ pub trait VotingRule<'a> {
         profile: &Profile<'a>,
         extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
     ) -> Bool<'a> {
-        let score_a = self.score(partition_a, profile, extra_votes);
-        let score_b = self.score(partition_b, profile, extra_votes);
-        let condition = score_a._eq(&score_b);
-        condition
+        let table = self.score_table(profile, extra_votes);
+        table[partition_a]._eq(&table[partition_b])
     }
 
     /// Returns the condition for ```ranking``` to receive zero votes.
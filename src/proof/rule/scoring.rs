@@ -10,13 +10,14 @@ use z3::ast::Int;
 use z3::ast::Real;
 use z3::Context;
 
-use super::rule_trait::VotingRule;
+use super::rule_trait::{RuleId, VotingRule};
 use itertools::Itertools;
 pub struct Scoring<'b> {
     pub partitions: BTreeSet<Partition>,
     pub alternatives: usize,
     pub num_candidates: usize,
     scores: BTreeMap<Vec<usize>, Vec<Real<'b>>>,
+    id: RuleId,
 }
 
 impl<'a> Scoring<'a> {
@@ -44,6 +45,7 @@ impl<'a> Scoring<'a> {
             alternatives: num_partitions,
             num_candidates: m,
             scores,
+            id: RuleId::next(),
         }
     }
 
@@ -53,13 +55,6 @@ impl<'a> Scoring<'a> {
 }
 
 impl<'a> VotingRule<'a> for Scoring<'a> {
-    fn new(m: usize) -> Self
-    where
-        Self: Sized,
-    {
-        todo!()
-    }
-
     fn name() -> &'static str
     where
         Self: Sized,
@@ -71,6 +66,10 @@ impl<'a> VotingRule<'a> for Scoring<'a> {
         self.partitions.clone()
     }
 
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
     fn score(
         &self,
         partition: &Partition,
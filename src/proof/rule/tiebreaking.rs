@@ -0,0 +1,184 @@
+use crate::proof::concrete::Votes;
+use crate::structures::Ranking;
+use crate::structures::Structure;
+use crate::{proof::profile::Profile, structures::Partition};
+use std::collections::{BTreeMap, BTreeSet};
+use z3::ast::{Ast, Bool, Int, Real};
+
+use super::rule_trait::{RuleId, VotingRule};
+
+/// Turns a voting rule into a resolute one.
+/// Ties are broken lexicographically by the scores of a secondary rule and then by a fixed priority order over the partitions.
+pub struct TieBreaking<'r, 'a> {
+    pub partitions: BTreeSet<Partition>,
+    pub alternatives: usize,
+    pub num_candidates: usize,
+    rule: &'r dyn VotingRule<'a>,
+    secondary: Option<&'r dyn VotingRule<'a>>,
+    priority: Vec<Partition>,
+    id: RuleId,
+}
+
+impl<'r, 'a> TieBreaking<'r, 'a> {
+    /// Creates a new tie-breaking rule. The priority order is the order of ```Partition::all(m)```.
+    pub fn create(
+        m: usize,
+        rule: &'r dyn VotingRule<'a>,
+        secondary: Option<&'r dyn VotingRule<'a>>,
+    ) -> Self {
+        let partitions = Partition::all(m);
+        let priority = partitions.iter().cloned().collect::<Vec<_>>();
+
+        TieBreaking {
+            alternatives: partitions.len(),
+            partitions,
+            num_candidates: m,
+            rule,
+            secondary,
+            priority,
+            id: RuleId::next(),
+        }
+    }
+
+    /// Returns the position of ```partition``` in the priority order, lower is better.
+    fn priority(&self, partition: &Partition) -> usize {
+        self.priority
+            .iter()
+            .position(|x| x == partition)
+            .unwrap_or_else(|| panic!("Partition {:?} has no priority", partition))
+    }
+
+    /// Returns the condition for ```partition``` to be preferred over ```other``` by the tie-breaking.
+    fn breaks_tie(
+        &self,
+        partition: &Partition,
+        other: &Partition,
+        profile: &Profile<'a>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Bool<'a> {
+        let ctx = profile.get_ctx();
+        let by_priority = Bool::from_bool(ctx, self.priority(partition) < self.priority(other));
+        match self.secondary {
+            None => by_priority,
+            Some(secondary) => {
+                let table = secondary.score_table(profile, extra_votes);
+                let (score, other_score) = (&table[partition], &table[other]);
+                let tied = score._eq(other_score);
+                Bool::or(
+                    ctx,
+                    &[
+                        &score.gt(other_score),
+                        &Bool::and(ctx, &[&tied, &by_priority]),
+                    ],
+                )
+            }
+        }
+    }
+}
+
+impl<'r, 'a> VotingRule<'a> for TieBreaking<'r, 'a> {
+    fn name() -> &'static str
+    where
+        Self: Sized,
+    {
+        "tiebreaking"
+    }
+
+    /// The priority order depends on the names of the candidates.
+    fn is_neutral(&self) -> bool {
+        false
+    }
+
+    fn all_partitions(&self) -> BTreeSet<Partition> {
+        self.partitions.clone()
+    }
+
+    fn id(&self) -> RuleId {
+        self.id
+    }
+
+    /// Returns the score of the underlying rule.
+    fn score(
+        &self,
+        partition: &Partition,
+        profile: &Profile<'a>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Real<'a> {
+        self.rule.score(partition, profile, extra_votes)
+    }
+
+    fn concrete_score(&self, partition: &Partition, votes: &Votes) -> Option<f64> {
+        self.rule.concrete_score(partition, votes)
+    }
+
+    /// Returns the winner after breaking the ties.
+    fn concrete_winners(&self, votes: &Votes) -> Option<BTreeSet<Partition>> {
+        let mut winners = self
+            .rule
+            .concrete_winners(votes)?
+            .into_iter()
+            .map(|partition| {
+                let score = match self.secondary {
+                    None => 0.0,
+                    Some(secondary) => secondary.concrete_score(&partition, votes)?,
+                };
+                Some((partition, score))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        // Higher secondary scores first, then the priority order.
+        winners.sort_by(|(a, score_a), (b, score_b)| {
+            score_b
+                .total_cmp(score_a)
+                .then(self.priority(a).cmp(&self.priority(b)))
+        });
+        Some(winners.into_iter().take(1).map(|(x, _)| x).collect())
+    }
+
+    /// Returns the condition for ```partition``` to be the winner after breaking the ties.
+    fn winner(
+        &self,
+        partition: &Partition,
+        profile: &Profile<'a>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Bool<'a> {
+        let ctx = profile.get_ctx();
+        let winner = self.rule.winner(partition, profile, extra_votes);
+
+        // Every other winner of the underlying rule must lose the tie-breaking.
+        let ties_broken = self
+            .all_partitions()
+            .iter()
+            .filter(|other| *other != partition)
+            .map(|other| {
+                let other_winner = self.rule.winner(other, profile, extra_votes);
+                other_winner.implies(&self.breaks_tie(partition, other, profile, extra_votes))
+            })
+            .collect::<Vec<_>>();
+
+        let conditions = std::iter::once(&winner)
+            .chain(ties_broken.iter())
+            .collect::<Vec<_>>();
+        Bool::and(ctx, &conditions)
+    }
+
+    /// The tie-breaking orders all partitions, therefore two different partitions are never tied.
+    fn tied(
+        &'a self,
+        partition_a: &Partition,
+        partition_b: &Partition,
+        profile: &Profile<'a>,
+        _extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Bool<'a> {
+        Bool::from_bool(profile.get_ctx(), partition_a == partition_b)
+    }
+
+    /// The winner is unique, therefore every winner is the only winner.
+    fn only_winner(
+        &self,
+        partition: &Partition,
+        profile: &Profile<'a>,
+        extra_votes: Option<&BTreeMap<Ranking, Int<'a>>>,
+    ) -> Bool<'a> {
+        self.winner(partition, profile, extra_votes)
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use z3::SatResult;
+
+use crate::proof::Proof;
+
+type AxiomSet = BTreeSet<&'static str>;
+
+/// The result of checking all combinations of the axioms of a proof.
+#[derive(Debug, Default)]
+pub struct Sweep {
+    /// Satisfiable sets which are not contained in another satisfiable set.
+    pub maximal_satisfiable: Vec<AxiomSet>,
+    /// Unsatisfiable sets which do not contain another unsatisfiable set.
+    pub minimal_unsatisfiable: Vec<AxiomSet>,
+    /// Sets for which the solver returned unknown.
+    pub unknown: Vec<AxiomSet>,
+    /// Number of sets which were checked by the solver.
+    pub checked: usize,
+}
+
+/// Returns the sizes of the sets in the order they are checked.
+/// The sizes alternate between small and large sets, such that both unsatisfiable and satisfiable sets can be used for pruning.
+fn size_order(n: usize) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    let (mut low, mut high) = (1, n);
+    while low <= high {
+        sizes.push(low);
+        if low != high {
+            sizes.push(high);
+        }
+        low += 1;
+        high -= 1;
+    }
+    sizes
+}
+
+/// Checks every non-empty subset of the axioms previously added to the proof.
+/// Supersets of unsatisfiable sets are unsatisfiable and subsets of satisfiable sets are satisfiable, these are not checked.
+pub fn sweep(proof: &Proof) -> Sweep {
+    let axioms = proof.axiom_names().into_iter().collect::<AxiomSet>();
+
+    let mut satisfiable: Vec<AxiomSet> = Vec::new();
+    let mut unsatisfiable: Vec<AxiomSet> = Vec::new();
+    let mut sweep = Sweep::default();
+
+    for size in size_order(axioms.len()) {
+        for subset in axioms.iter().cloned().combinations(size) {
+            let subset = subset.into_iter().collect::<AxiomSet>();
+            if unsatisfiable.iter().any(|x| x.is_subset(&subset))
+                || satisfiable.iter().any(|x| x.is_superset(&subset))
+            {
+                continue;
+            }
+
+            log::info!("Checking {}", subset.iter().join(", "));
+            sweep.checked += 1;
+            match proof.check_subset(&subset.iter().cloned().collect::<Vec<_>>()) {
+                SatResult::Sat => satisfiable.push(subset),
+                SatResult::Unsat => unsatisfiable.push(subset),
+                SatResult::Unknown => sweep.unknown.push(subset),
+            }
+        }
+    }
+
+    sweep.maximal_satisfiable = satisfiable
+        .iter()
+        .filter(|x| !satisfiable.iter().any(|y| y != *x && y.is_superset(x)))
+        .cloned()
+        .collect();
+    sweep.minimal_unsatisfiable = unsatisfiable
+        .iter()
+        .filter(|x| !unsatisfiable.iter().any(|y| y != *x && y.is_subset(x)))
+        .cloned()
+        .collect();
+    sweep
+}
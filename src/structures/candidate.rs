@@ -1,12 +1,44 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
 
 use super::Structure;
 
+/// A relabeling of the candidates, every candidate is mapped to its new name.
+pub type Permutation = BTreeMap<Candidate, Candidate>;
+
+/// Returns the smallest relabeling of a structure, structures relabeling each other have the same canonical form.
+pub fn canonical<T: Ord>(permutations: &[Permutation], relabel: impl Fn(&Permutation) -> T) -> T {
+    permutations
+        .iter()
+        .map(relabel)
+        .min()
+        .expect("There is at least the identity")
+}
+
 #[derive(Eq, Hash, Ord, PartialEq, PartialOrd, Clone, Debug)]
 pub struct Candidate {
     pub name: String,
 }
 
+impl Candidate {
+    /// Returns all m! permutations of the candidates, starting with the identity.
+    pub fn permutations(m: usize) -> Vec<Permutation> {
+        let candidates = Candidate::all(m);
+        candidates
+            .iter()
+            .cloned()
+            .permutations(m)
+            .map(|image| candidates.iter().cloned().zip(image).collect())
+            .collect()
+    }
+
+    /// Returns the candidate ```self``` is mapped to by ```permutation```.
+    pub fn permute(&self, permutation: &Permutation) -> Candidate {
+        permutation.get(self).unwrap_or(self).clone()
+    }
+}
+
 impl Structure for Candidate {
     fn all(m: usize) -> BTreeSet<Self>
     where
@@ -3,7 +3,7 @@ use std::collections::BTreeSet;
 use itertools::Itertools;
 
 use crate::{
-    structures::candidate::Candidate,
+    structures::candidate::{Candidate, Permutation},
     utils::{structures::powerset, unordered_pair::UnorderedPair},
 };
 
@@ -40,6 +40,17 @@ impl Coalition {
             .map(|(a, b)| UnorderedPair::new(a, b))
             .collect()
     }
+
+    /// Returns the coalition with every candidate relabeled by ```permutation```.
+    pub fn permute(&self, permutation: &Permutation) -> Coalition {
+        Coalition {
+            members: self
+                .members
+                .iter()
+                .map(|x| x.permute(permutation))
+                .collect(),
+        }
+    }
 }
 
 impl Structure for Coalition {
@@ -4,7 +4,7 @@ pub mod partition;
 pub mod ranking;
 mod utils;
 
-pub use candidate::Candidate;
+pub use candidate::{canonical, Candidate, Permutation};
 pub use coalition::Coalition;
 pub use partition::Partition;
 pub use ranking::Ranking;
@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 
-use super::{Candidate, Structure};
+use super::{Candidate, Permutation, Structure};
 use crate::{
     structures::coalition::Coalition,
     utils::{structures::partition, unordered_pair::UnorderedPair},
@@ -60,6 +60,76 @@ impl Partition {
             .all(|x| !self.contains_pair(&(x.0, x.1)))
     }
 
+    /// Returns true if the partition is a refinement of ```other```, i.e. every coalition is contained in a coalition of ```other```.
+    pub fn is_refinement_of(&self, other: &Partition) -> bool {
+        self.coalitions.iter().all(|x| other.contains_weak(x))
+    }
+
+    /// Returns true if the partition is a refinement of ```other``` and not equal to ```other```.
+    pub fn is_strict_refinement_of(&self, other: &Partition) -> bool {
+        self != other && self.is_refinement_of(other)
+    }
+
+    /// Returns the meet of the partitions, i.e. the coarsest common refinement.
+    pub fn meet(&self, other: &Partition) -> Partition {
+        let coalitions = self
+            .coalitions
+            .iter()
+            .cartesian_product(other.coalitions.iter())
+            .filter_map(|(a, b)| {
+                let members = a
+                    .members
+                    .intersection(&b.members)
+                    .cloned()
+                    .collect::<BTreeSet<_>>();
+                if members.is_empty() {
+                    None
+                } else {
+                    Some(Coalition { members })
+                }
+            })
+            .collect();
+        Partition { coalitions }
+    }
+
+    /// Returns the join of the partitions, i.e. the finest common coarsening.
+    pub fn join(&self, other: &Partition) -> Partition {
+        let mut classes = self
+            .coalitions
+            .iter()
+            .map(|x| x.members.clone())
+            .collect::<Vec<_>>();
+
+        // Every coalition of other merges all classes it intersects.
+        for coalition in &other.coalitions {
+            let (overlapping, mut rest): (Vec<_>, Vec<_>) = classes
+                .into_iter()
+                .partition(|x| !x.is_disjoint(&coalition.members));
+            let mut merged = coalition.members.clone();
+            overlapping.into_iter().for_each(|x| merged.extend(x));
+            rest.push(merged);
+            classes = rest;
+        }
+
+        Partition {
+            coalitions: classes
+                .into_iter()
+                .map(|members| Coalition { members })
+                .collect(),
+        }
+    }
+
+    /// Returns the partition with every candidate relabeled by ```permutation```.
+    pub fn permute(&self, permutation: &Permutation) -> Partition {
+        Partition {
+            coalitions: self
+                .coalitions
+                .iter()
+                .map(|x| x.permute(permutation))
+                .collect(),
+        }
+    }
+
     /// Returns all partitions that are one editing distance away from the partition.
     pub fn distance_one(&self) -> BTreeSet<Partition> {
         let mut result = BTreeSet::new();
@@ -6,7 +6,7 @@ use crate::{
 };
 use std::collections::BTreeSet;
 
-use super::{Candidate, Coalition, Structure};
+use super::{Candidate, Coalition, Permutation, Structure};
 
 #[derive(Eq, Hash, Ord, PartialEq, PartialOrd, Clone, Debug)]
 enum IndiffTypes {
@@ -168,6 +168,17 @@ impl Ranking {
         });
         *result.unwrap() != IndiffTypes::Failed
     }
+
+    /// Returns the ranking with every candidate relabeled by ```permutation```.
+    pub fn permute(&self, permutation: &Permutation) -> Ranking {
+        Ranking {
+            ranking: self
+                .ranking
+                .iter()
+                .map(|equiv| equiv.iter().map(|x| x.permute(permutation)).collect())
+                .collect(),
+        }
+    }
 }
 
 impl Structure for Ranking {
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::OnceCell;
+    use std::collections::BTreeMap;
+
+    use partitionElection::ballots::{Ballot, Fcs, Fp, Pa};
+    use partitionElection::proof::axiom::{
+        Axiom, Cancellation, CondorcetLoser, Consistency, FullCoalitionSupport, Majority,
+        MeetSupport, Participation, RefinementDominance, Resoluteness, SmithConsistency,
+        Strategyproof, StrictAllSplit, StrictSomeSplit, StrongPairSupport, Unanimity, WeakAllSplit,
+        WeakPairSupport, WeakSomeSplit,
+    };
+    use partitionElection::proof::concrete::{profiles, Votes};
+    use partitionElection::proof::profile::Profile;
+    use partitionElection::proof::rule::{
+        Borda, Copeland, RuleConstructor, TieBreaking, VotingRule,
+    };
+    use partitionElection::structures::Ranking;
+    use z3::{ast::Ast, SatResult, Solver};
+
+    /// The number of sampled profiles with up to ```MAX_VOTERS``` voters, besides every profile with one voter.
+    const SAMPLES: usize = 32;
+    /// The number of sampled profiles the axioms quantifying over further profiles are compared on.
+    const QUANTIFIED_SAMPLES: usize = 6;
+    const MAX_VOTERS: u64 = 5;
+
+    /// Linear congruential generator, such that the sampled profiles are the same in every run.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 33) % bound
+        }
+    }
+
+    /// Samples profiles with 1 to ```MAX_VOTERS``` voters, every voter picks a ranking uniformly.
+    fn sample_profiles(rankings: &[Ranking], rng: &mut Lcg) -> Vec<Votes> {
+        (0..SAMPLES)
+            .map(|_| {
+                let voters = 1 + rng.next(MAX_VOTERS);
+                let mut votes = Votes::new();
+                for _ in 0..voters {
+                    let ranking = &rankings[rng.next(rankings.len() as u64) as usize];
+                    *votes.entry(ranking.clone()).or_insert(0) += 1;
+                }
+                votes
+            })
+            .collect()
+    }
+
+    /// Returns whether Z3 finds no violated condition of the axiom in the profile with fixed votes.
+    /// Conditions without free variables are decided by simplification, the others by the solver.
+    fn z3_holds<'a, A: Axiom>(rule: &dyn VotingRule<'a>, profile: &Profile<'a>) -> bool {
+        let ctx = profile.get_ctx();
+        let rule = Box::new(rule);
+        // Creating the solver dominates the conditions decided by simplification, so it is created on demand.
+        let solver = OnceCell::new();
+        let holds = A::condition_generator(profile, &rule).all(|condition| {
+            if let Some(holds) = condition.simplify().as_bool() {
+                return holds;
+            }
+            let solver = solver.get_or_init(|| {
+                let solver = Solver::new(ctx);
+                solver.assert(&profile.preconditions());
+                solver
+            });
+            solver.push();
+            solver.assert(&condition.not());
+            let holds = solver.check() == SatResult::Unsat;
+            solver.pop(1);
+            holds
+        });
+        holds
+    }
+
+    /// Compares Z3 with the concrete evaluation of the axiom, returns a description of every disagreement.
+    fn compare<'a, A: Axiom>(
+        rule_name: &str,
+        rule: &dyn VotingRule<'a>,
+        profile: &Profile<'a>,
+        votes: &Votes,
+    ) -> Option<String> {
+        let concrete = A::holds_concretely(rule, profile, votes)?;
+        let z3 = z3_holds::<A>(rule, profile);
+        (z3 != concrete).then(|| {
+            format!(
+                "{} {}: Z3 {}, concrete {} for {:?}",
+                rule_name,
+                A::short_name(),
+                z3,
+                concrete,
+                votes
+                    .iter()
+                    .map(|(ranking, count)| format!("{} -> {}", count, ranking.to_string()))
+                    .collect::<Vec<_>>()
+            )
+        })
+    }
+
+    fn differential<B: Ballot>(seed: u64) {
+        let m = 3;
+        let ctx = z3::Context::new(&z3::Config::new());
+        let rankings = B::all_rankings(m).into_iter().collect::<Vec<_>>();
+        let borda = Borda::new(m);
+        let copeland = Copeland::new(m);
+        let tie_breaking = TieBreaking::create(m, &borda, Some(&copeland));
+        let rules: [(&str, &dyn VotingRule); 3] = [
+            ("borda", &borda),
+            ("copeland", &copeland),
+            ("borda+copeland", &tie_breaking),
+        ];
+
+        let sampled = sample_profiles(&rankings, &mut Lcg(seed));
+        let cases = sampled
+            .into_iter()
+            .enumerate()
+            .map(|(i, votes)| (votes, i < QUANTIFIED_SAMPLES))
+            .chain(profiles(&rankings, 1).map(|votes| (votes, false)));
+
+        let mut mismatches = Vec::new();
+        for (votes, quantified) in cases {
+            // Rankings without votes are part of the profile, a new voter may pick them.
+            let counts = rankings
+                .iter()
+                .map(|ranking| {
+                    let count = votes.get(ranking).copied().unwrap_or(0);
+                    (ranking.clone(), count as usize)
+                })
+                .collect::<BTreeMap<_, _>>();
+            let profile = Profile::from_custom(m, &ctx, counts);
+
+            for (name, rule) in rules {
+                mismatches.extend([
+                    compare::<Unanimity>(name, rule, &profile, &votes),
+                    compare::<Majority>(name, rule, &profile, &votes),
+                    compare::<Resoluteness>(name, rule, &profile, &votes),
+                    compare::<Cancellation>(name, rule, &profile, &votes),
+                    compare::<FullCoalitionSupport>(name, rule, &profile, &votes),
+                    compare::<WeakPairSupport>(name, rule, &profile, &votes),
+                    compare::<StrongPairSupport>(name, rule, &profile, &votes),
+                    compare::<SmithConsistency>(name, rule, &profile, &votes),
+                    compare::<CondorcetLoser>(name, rule, &profile, &votes),
+                    compare::<RefinementDominance>(name, rule, &profile, &votes),
+                    compare::<MeetSupport>(name, rule, &profile, &votes),
+                    compare::<WeakSomeSplit>(name, rule, &profile, &votes),
+                    compare::<WeakAllSplit>(name, rule, &profile, &votes),
+                    compare::<StrictSomeSplit>(name, rule, &profile, &votes),
+                    compare::<StrictAllSplit>(name, rule, &profile, &votes),
+                ]);
+                // The axioms quantifying over further profiles need the solver, they are only compared on the first samples.
+                if quantified {
+                    mismatches.extend([
+                        compare::<Consistency>(name, rule, &profile, &votes),
+                        compare::<Participation>(name, rule, &profile, &votes),
+                        compare::<Strategyproof>(name, rule, &profile, &votes),
+                    ]);
+                }
+            }
+        }
+        let mismatches = mismatches.into_iter().flatten().collect::<Vec<_>>();
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+
+    #[test]
+    fn test_differential_fp() {
+        differential::<Fp>(1);
+    }
+
+    #[test]
+    fn test_differential_pa() {
+        differential::<Pa>(2);
+    }
+
+    #[test]
+    fn test_differential_fcs() {
+        differential::<Fcs>(3);
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use partitionElection::ballots::get_profile;
+    use partitionElection::proof::axiom::check_iteratively;
+    use partitionElection::proof::rule::{Borda, Copeland, RuleConstructor, VotingRule};
+    use partitionElection::proof::Proof;
+    use z3::SatResult::{self, Sat, Unsat};
+
+    /// Known results for m=3 as (rule, axiom, result), ```Unsat``` means the rule violates the axiom.
+    /// Combinations taking minutes to check are omitted: borda with strat on FCS, PA and PS, and copeland with strat on PA.
+    type Golden = [(&'static str, &'static str, SatResult)];
+
+    const FP: &Golden = &[
+        ("borda", "unam", Sat),
+        ("borda", "maj", Unsat),
+        ("borda", "part", Sat),
+        ("borda", "cons", Sat),
+        ("borda", "strat", Unsat),
+        ("copeland", "unam", Sat),
+        ("copeland", "maj", Sat),
+        ("copeland", "part", Sat),
+        ("copeland", "cons", Sat),
+        ("copeland", "strat", Sat),
+    ];
+
+    const FCW: &Golden = &[
+        ("borda", "unam", Sat),
+        ("borda", "maj", Sat),
+        ("borda", "part", Sat),
+        ("borda", "cons", Sat),
+        ("borda", "strat", Sat),
+        ("copeland", "unam", Sat),
+        ("copeland", "maj", Sat),
+        ("copeland", "part", Sat),
+        ("copeland", "cons", Sat),
+        ("copeland", "strat", Sat),
+    ];
+
+    const FCS: &Golden = &[
+        ("borda", "unam", Sat),
+        ("borda", "maj", Unsat),
+        ("borda", "part", Sat),
+        ("borda", "cons", Sat),
+        ("copeland", "unam", Sat),
+        ("copeland", "maj", Sat),
+        ("copeland", "part", Sat),
+        ("copeland", "cons", Sat),
+        ("copeland", "strat", Sat),
+    ];
+
+    const PA: &Golden = &[
+        ("borda", "unam", Sat),
+        ("borda", "maj", Unsat),
+        ("borda", "part", Sat),
+        ("borda", "cons", Sat),
+        ("copeland", "unam", Sat),
+        ("copeland", "maj", Sat),
+        ("copeland", "part", Sat),
+        ("copeland", "cons", Sat),
+    ];
+
+    const PS: &Golden = &[
+        ("borda", "unam", Sat),
+        ("borda", "maj", Unsat),
+        ("borda", "part", Sat),
+        ("borda", "cons", Sat),
+        ("copeland", "unam", Sat),
+        ("copeland", "maj", Unsat),
+        ("copeland", "part", Unsat),
+        ("copeland", "cons", Unsat),
+        ("copeland", "strat", Unsat),
+    ];
+
+    fn check_golden(ballot: &str, golden: &Golden) {
+        let borda = Borda::new(3);
+        let copeland = Copeland::new(3);
+
+        let mut flipped = Vec::new();
+        for (rule_name, axiom, expected) in golden {
+            let rule: &dyn VotingRule = match *rule_name {
+                "borda" => &borda,
+                _ => &copeland,
+            };
+            let ctx = z3::Context::new(&z3::Config::new());
+            let mut proof = Proof::new(3, rule, get_profile(3, ballot, &ctx));
+            let result = check_iteratively(axiom, &mut proof).result;
+            if result != *expected {
+                flipped.push(format!(
+                    "{} {} {}: expected {:?}, got {:?}",
+                    ballot, rule_name, axiom, expected, result
+                ));
+            }
+        }
+        assert!(flipped.is_empty(), "{}", flipped.join("\n"));
+    }
+
+    #[test]
+    fn test_golden_fp() {
+        check_golden("FP", FP);
+    }
+
+    #[test]
+    fn test_golden_fcw() {
+        check_golden("FCW", FCW);
+    }
+
+    #[test]
+    fn test_golden_fcs() {
+        check_golden("FCS", FCS);
+    }
+
+    #[test]
+    fn test_golden_pa() {
+        check_golden("PA", PA);
+    }
+
+    #[test]
+    fn test_golden_ps() {
+        check_golden("PS", PS);
+    }
+}
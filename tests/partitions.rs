@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use partitionElection::structures::Structure;
+    use partitionElection::structures::{Candidate, Partition};
+
+    #[test]
+    fn test_refinement() {
+        let fine = Partition::from_str("{{a}, {b}, {c}}").unwrap();
+        let middle = Partition::from_str("{{a, b}, {c}}").unwrap();
+        let coarse = Partition::from_str("{{a, b, c}}").unwrap();
+        let other = Partition::from_str("{{a}, {b, c}}").unwrap();
+
+        assert!(fine.is_strict_refinement_of(&middle));
+        assert!(middle.is_strict_refinement_of(&coarse));
+        assert!(middle.is_refinement_of(&middle));
+        assert!(!middle.is_strict_refinement_of(&middle));
+        assert!(!middle.is_refinement_of(&other));
+        assert!(!coarse.is_refinement_of(&fine));
+    }
+
+    #[test]
+    fn test_meet_join() {
+        let ab = Partition::from_str("{{a, b}, {c}}").unwrap();
+        let bc = Partition::from_str("{{a}, {b, c}}").unwrap();
+
+        assert_eq!(
+            ab.meet(&bc),
+            Partition::from_str("{{a}, {b}, {c}}").unwrap()
+        );
+        assert_eq!(ab.join(&bc), Partition::from_str("{{a, b, c}}").unwrap());
+    }
+
+    #[test]
+    fn test_permute() {
+        let permutations = Candidate::permutations(3);
+        assert_eq!(permutations.len(), 6);
+
+        let ab = Partition::from_str("{{a, b}, {c}}").unwrap();
+        assert_eq!(ab.permute(&permutations[0]), ab);
+        let images = permutations
+            .iter()
+            .map(|permutation| ab.permute(permutation))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(images.len(), 3);
+
+        let partitions = Partition::all(4);
+        for permutation in Candidate::permutations(4) {
+            let permuted = partitions
+                .iter()
+                .map(|x| x.permute(&permutation))
+                .collect::<std::collections::BTreeSet<_>>();
+            assert_eq!(permuted, partitions);
+        }
+    }
+
+    #[test]
+    fn test_lattice() {
+        let m = 4;
+        let partitions = Partition::all(m);
+        for a in &partitions {
+            for b in &partitions {
+                let meet = a.meet(b);
+                let join = a.join(b);
+                assert!(meet.is_legal(m) && join.is_legal(m));
+                assert!(meet.is_refinement_of(a) && meet.is_refinement_of(b));
+                assert!(a.is_refinement_of(&join) && b.is_refinement_of(&join));
+                assert_eq!(a.is_refinement_of(b), a.meet(b) == *a);
+                assert_eq!(a.is_refinement_of(b), a.join(b) == *b);
+            }
+        }
+    }
+}
@@ -0,0 +1,433 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use itertools::Itertools;
+    use partitionElection::ballots::get_profile;
+    use partitionElection::proof::axiom::{
+        Axiom, Consistency, CoreStability, FullCoalitionSupport, Majority, Nonimposition,
+        Resoluteness, SmithConsistency, Strategyproof, StrictAllSplit, StrictSomeSplit,
+        StrongPairSupport, Unanimity, WeakAllSplit, WeakPairSupport, WeakSomeSplit,
+    };
+    use partitionElection::proof::checkpoint::Checkpoint;
+    use partitionElection::proof::concrete::total_votes;
+    use partitionElection::proof::minimize::{minimize, votes_of};
+    use partitionElection::proof::parallel::{check_iteratively_parallel, BuildProof};
+    use partitionElection::proof::profile::Profile;
+    use partitionElection::proof::rule::{Borda, Copeland, RuleConstructor, VotingRule};
+    use partitionElection::proof::{Limits, Proof};
+    use partitionElection::structures::{Candidate, Coalition, Partition, Permutation};
+    use z3::ast::{Ast, Bool, Int};
+    use z3::SatResult;
+
+    #[test]
+    fn test_counterexample() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = get_profile(3, "FP", &ctx);
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, profile);
+
+        let outcome = proof.check_iteratively::<Resoluteness>();
+
+        assert_eq!(outcome.result, SatResult::Unsat);
+        let failed = outcome.failed_condition().unwrap();
+        assert_eq!(failed.index, outcome.conditions.len() - 1);
+        assert!(failed.profile.is_some());
+        assert!(failed.winners.as_ref().unwrap().len() > 1);
+        assert_eq!(failed.verified, Some(true));
+    }
+
+    #[test]
+    fn test_all_conditions_hold() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = get_profile(3, "FP", &ctx);
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, profile);
+
+        let outcome = proof.check_iteratively::<Unanimity>();
+
+        assert_eq!(outcome.result, SatResult::Sat);
+        assert!(outcome.failed_condition().is_none());
+        assert!(outcome.conditions.iter().all(|x| x.held == Some(true)));
+    }
+
+    #[test]
+    fn test_bounded_electorate() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = get_profile(3, "FP", &ctx);
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, profile);
+
+        proof.set_voters(Some(1));
+        let outcome = proof.check_iteratively::<Resoluteness>();
+        assert_eq!(outcome.result, SatResult::Sat);
+
+        proof.set_voters(Some(2));
+        let outcome = proof.check_iteratively::<Resoluteness>();
+        assert_eq!(outcome.result, SatResult::Unsat);
+        let failed = outcome.failed_condition().unwrap();
+        assert_eq!(failed.profile.as_ref().unwrap().values().sum::<u64>(), 2);
+    }
+
+    #[test]
+    fn test_smallest_electorate() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut profile = get_profile(3, "FP", &ctx);
+        profile.max_voters = Some(3);
+        let mut proof = Proof::new(3, &borda, profile);
+
+        let (outcome, smallest) = proof.smallest_electorate::<Resoluteness>();
+        assert_eq!(outcome.result, SatResult::Unsat);
+        assert_eq!(smallest, Some(2));
+
+        // The check with one voter is undecided, so the violation with two voters is not reported as the smallest.
+        proof.set_limits(Limits {
+            total_timeout: Some(0),
+            ..Limits::default()
+        });
+        let (outcome, smallest) = proof.smallest_electorate::<Resoluteness>();
+        assert_eq!(outcome.result, SatResult::Unknown);
+        assert_eq!(smallest, None);
+    }
+
+    #[test]
+    fn test_minimal_counterexample() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = get_profile(3, "PA", &ctx);
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, profile);
+        proof.set_minimize(true);
+
+        let outcome = proof.check_iteratively::<SmithConsistency>();
+
+        assert_eq!(outcome.result, SatResult::Unsat);
+        let profile = outcome
+            .failed_condition()
+            .unwrap()
+            .profile
+            .as_ref()
+            .unwrap();
+        assert_eq!(profile.values().sum::<u64>(), 3);
+        assert_eq!(profile.len(), 2);
+        assert_eq!(outcome.failed_condition().unwrap().verified, Some(true));
+    }
+
+    #[test]
+    fn test_minimize_respects_limit() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = get_profile(3, "PA", &ctx);
+        let at_least_four = profile.total_votes().ge(&Int::from_u64(&ctx, 4));
+        let formula = Bool::and(&ctx, &[&profile.preconditions(), &at_least_four]);
+
+        let model = minimize(&profile, &formula, &|_| true).unwrap();
+        let votes = votes_of(&profile, &model);
+        assert_eq!(votes.values().sum::<u64>(), 4);
+        assert_eq!(votes.len(), 1);
+
+        assert!(minimize(&profile, &formula, &|_| false).is_none());
+
+        // Once the time is up, the first model found is returned.
+        let checks = Cell::new(0);
+        let limit = |_: &z3::Solver| {
+            checks.set(checks.get() + 1);
+            checks.get() == 1
+        };
+        let model = minimize(&profile, &formula, &limit).unwrap();
+        assert!(votes_of(&profile, &model).values().sum::<u64>() >= 4);
+        assert_eq!(checks.get(), 2);
+    }
+
+    #[test]
+    fn test_minimal_unsat_subset() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+        proof.add_axiom::<Unanimity>();
+        proof.add_axiom::<Resoluteness>();
+
+        let result = proof.check();
+        assert_eq!(result.result, SatResult::Unsat);
+        let subset = proof.minimal_unsat_subset(&result.unsat_core);
+        assert!(subset.minimal);
+        assert_eq!(subset.axioms, vec![Resoluteness::short_name()]);
+
+        // Every removal is unknown once the total timeout is exceeded.
+        proof.set_limits(Limits {
+            total_timeout: Some(0),
+            ..Limits::default()
+        });
+        let subset = proof.minimal_unsat_subset(&result.unsat_core);
+        assert!(!subset.minimal);
+        assert_eq!(subset.axioms, result.unsat_core);
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+        let serial = proof.check_iteratively::<Resoluteness>();
+
+        let build: &BuildProof = &|ctx| {
+            let borda = Box::leak(Box::new(Borda::new(3)));
+            Proof::new(3, borda, get_profile(3, "FP", ctx))
+        };
+        let parallel = check_iteratively_parallel::<Resoluteness>(3, build).unwrap();
+
+        assert_eq!(parallel.result, serial.result);
+        assert_eq!(parallel.conditions.len(), serial.conditions.len());
+        assert_eq!(
+            parallel.failed_condition().unwrap().index,
+            serial.failed_condition().unwrap().index
+        );
+        assert!(check_iteratively_parallel::<Resoluteness>(0, build).is_err());
+    }
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+        let path = std::env::temp_dir().join(format!(
+            "partition_election_test_checkpoint_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "settings\n0\n1\n2\n").unwrap();
+
+        let checkpoint = Checkpoint::open(&path, "settings", true).unwrap();
+        assert!(checkpoint.is_completed(2));
+        assert!(!checkpoint.is_completed(3));
+        proof.set_checkpoint(Some(checkpoint));
+        let outcome = proof.check_iteratively::<Unanimity>();
+
+        assert_eq!(outcome.result, SatResult::Sat);
+        assert!(outcome.conditions[..3].iter().all(|x| x.held == Some(true)));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resume_from_truncated_checkpoint() {
+        let path = std::env::temp_dir().join(format!(
+            "partition_election_test_truncated_checkpoint_{}.txt",
+            std::process::id()
+        ));
+        // The run was interrupted while writing the index 12.
+        std::fs::write(&path, "settings\n0\n1\n12").unwrap();
+
+        let mut checkpoint = Checkpoint::open(&path, "settings", true).unwrap();
+        assert!(checkpoint.is_completed(1));
+        assert!(!checkpoint.is_completed(12));
+        checkpoint.record(13);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "settings\n0\n1\n13\n"
+        );
+
+        let checkpoint = Checkpoint::open(&path, "settings", true).unwrap();
+        assert!(checkpoint.is_completed(13));
+        assert!(!checkpoint.is_completed(1213));
+        checkpoint.remove();
+    }
+
+    #[test]
+    fn test_smt2_conditions() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+
+        let problems = proof.condition_smt2::<Resoluteness>();
+        let failed = proof
+            .check_iteratively::<Resoluteness>()
+            .failed_condition()
+            .unwrap()
+            .index;
+
+        // A forall condition fails iff its problem is satisfiable.
+        for (index, problem) in problems.iter().enumerate().take(failed + 1) {
+            let solver = z3::Solver::new(&ctx);
+            solver.from_string(problem.as_str());
+            let expected = if index == failed {
+                SatResult::Sat
+            } else {
+                SatResult::Unsat
+            };
+            assert_eq!(solver.check(), expected);
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+        proof.set_voters(Some(2));
+        let solver = proof.check_iteratively::<Resoluteness>();
+        let enumerated = proof.brute_force::<Resoluteness>();
+
+        assert_eq!(enumerated.result, solver.result);
+        let failed = enumerated.failed_condition().unwrap();
+        assert_eq!(failed.profile.as_ref().unwrap().values().sum::<u64>(), 2);
+        assert_eq!(failed.verified, Some(true));
+
+        proof.set_voters(Some(1));
+        assert_eq!(proof.brute_force::<Resoluteness>().result, SatResult::Sat);
+
+        assert!(StrictSomeSplit::evaluates_concretely());
+        assert_eq!(
+            proof.brute_force::<StrictSomeSplit>().result,
+            proof.check_iteratively::<StrictSomeSplit>().result
+        );
+        assert!(!Nonimposition::evaluates_concretely());
+    }
+
+    #[test]
+    fn test_symmetry_breaking() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let copeland = Copeland::new(3);
+        for rule in [&borda as &dyn VotingRule, &copeland] {
+            let mut proof = Proof::new(3, rule, get_profile(3, "FP", &ctx));
+            let all = [
+                proof.check_iteratively::<Unanimity>().result,
+                proof.check_iteratively::<Majority>().result,
+                proof.check_iteratively::<Strategyproof>().result,
+            ];
+            proof.set_symmetry(true);
+            let reduced = [
+                proof.check_iteratively::<Unanimity>().result,
+                proof.check_iteratively::<Majority>().result,
+                proof.check_iteratively::<Strategyproof>().result,
+            ];
+            assert_eq!(all, reduced);
+
+            let outcome = proof.check_iteratively::<Resoluteness>();
+            assert_eq!(outcome.result, SatResult::Unsat);
+            assert_eq!(outcome.failed_condition().unwrap().verified, Some(true));
+        }
+    }
+
+    #[test]
+    fn test_quantified_profiles() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let mut proof = Proof::new(3, &borda, get_profile(3, "FP", &ctx));
+        let outcome = proof.check_iteratively::<Strategyproof>();
+        let failed = outcome.failed_condition().unwrap();
+        let voters = total_votes(failed.profile.as_ref().unwrap());
+        assert_eq!(failed.quantified.len(), 2);
+        for quantified in failed.quantified.iter() {
+            assert_eq!(total_votes(&quantified.profile), voters + 1);
+            assert_eq!(
+                borda.concrete_winners(&quantified.profile).as_ref(),
+                Some(&quantified.winners)
+            );
+        }
+
+        let copeland = Copeland::new(3);
+        let mut proof = Proof::new(3, &copeland, get_profile(3, "PS", &ctx));
+        let outcome = proof.check_iteratively::<Consistency>();
+        let failed = outcome.failed_condition().unwrap();
+        let [first, second] = &failed.quantified[..] else {
+            panic!("consistency quantifies over two sub-profiles");
+        };
+        let mut sum = first.profile.clone();
+        for (ranking, count) in second.profile.iter() {
+            *sum.entry(ranking.clone()).or_insert(0) += count;
+        }
+        assert_eq!(Some(&sum), failed.profile.as_ref());
+        assert!(first.winners.intersection(&second.winners).count() > 0);
+        assert_eq!(failed.verified, Some(true));
+    }
+
+    /// Asserts that every condition has a key and that two conditions share a key iff the structures they are built from relabel each other.
+    fn assert_keyed<'a, A: Axiom, T: PartialEq>(
+        profile: &Profile<'a>,
+        rule: &dyn VotingRule<'a>,
+        structures: &[T],
+        permute: impl Fn(&T, &Permutation) -> T,
+    ) {
+        let keys = A::canonical_keys(profile).unwrap();
+        let rule = Box::new(rule);
+        assert_eq!(keys.len(), A::condition_generator(profile, &rule).count());
+        assert_eq!(keys.len(), structures.len());
+        let permutations = Candidate::permutations(profile.num_candidates);
+        for (a, key_a) in structures.iter().zip(&keys) {
+            for (b, key_b) in structures.iter().zip(&keys) {
+                let relabeled = permutations.iter().any(|x| permute(a, x) == *b);
+                assert_eq!(key_a == key_b, relabeled, "{} and {}", key_a, key_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deduplicate() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let profile = get_profile(3, "PA", &ctx);
+        let pairs = profile
+            .candidates
+            .iter()
+            .cartesian_product(profile.candidates.iter())
+            .filter(|pair| pair.0 != pair.1)
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .collect::<Vec<_>>();
+        let permute_pair =
+            |(a, b): &(Candidate, Candidate), x: &Permutation| (a.permute(x), b.permute(x));
+        let partitions = profile.partitions.iter().cloned().collect::<Vec<_>>();
+        let coalitions = profile.coalitions.iter().cloned().collect::<Vec<_>>();
+        let split_coalitions = coalitions
+            .iter()
+            .filter(|coalition| !coalition.members.is_empty() && coalition.members.len() != 3)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_keyed::<WeakPairSupport, _>(&profile, &borda, &pairs, permute_pair);
+        assert_keyed::<StrongPairSupport, _>(&profile, &borda, &pairs, permute_pair);
+        assert_keyed::<FullCoalitionSupport, _>(&profile, &borda, &coalitions, Coalition::permute);
+        assert_keyed::<WeakSomeSplit, _>(&profile, &borda, &split_coalitions, Coalition::permute);
+        assert_keyed::<WeakAllSplit, _>(&profile, &borda, &split_coalitions, Coalition::permute);
+        assert_keyed::<StrictSomeSplit, _>(&profile, &borda, &split_coalitions, Coalition::permute);
+        assert_keyed::<StrictAllSplit, _>(&profile, &borda, &split_coalitions, Coalition::permute);
+        assert_keyed::<Majority, _>(&profile, &borda, &partitions, Partition::permute);
+        assert_keyed::<Nonimposition, _>(&profile, &borda, &partitions, Partition::permute);
+
+        assert!(profile.closed_under_relabeling());
+        assert!(!get_profile(3, "PA", &ctx)
+            .with_candidate_voters()
+            .closed_under_relabeling());
+
+        let mut proof = Proof::new(3, &borda, profile);
+        let all = proof.check_iteratively::<StrictSomeSplit>();
+        proof.set_deduplicate(true);
+        let deduplicated = proof.check_iteratively::<StrictSomeSplit>();
+        assert_eq!(deduplicated.result, all.result);
+        // One singleton and one pair of candidates.
+        assert_eq!(all.conditions.len(), 6);
+        assert_eq!(deduplicated.conditions.len(), 2);
+
+        assert_eq!(
+            proof.check_iteratively::<Majority>().result,
+            SatResult::Unsat
+        );
+        assert_eq!(
+            proof.check_iteratively::<Nonimposition>().result,
+            SatResult::Sat
+        );
+    }
+
+    #[test]
+    fn test_core_requires_candidate_voters() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let borda = Borda::new(3);
+        let rule = Box::new(&borda as &dyn VotingRule);
+        assert!(CoreStability::requires_candidate_voters());
+
+        let profile = get_profile(3, "FP", &ctx);
+        assert_eq!(
+            CoreStability::condition_generator(&profile, &rule).count(),
+            0
+        );
+        let profile = profile.with_candidate_voters();
+        assert!(CoreStability::condition_generator(&profile, &rule).count() > 0);
+    }
+}
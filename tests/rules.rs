@@ -4,8 +4,8 @@ mod tests {
     use partitionElection::structures::Structure;
     use std::collections::{BTreeMap, BTreeSet};
 
-    use partitionElection::proof::rule::Borda;
     use partitionElection::proof::rule::VotingRule;
+    use partitionElection::proof::rule::{Borda, Copeland, RuleConstructor, TieBreaking};
     use partitionElection::structures::{Partition, Ranking};
 
     use z3::ast::Ast;
@@ -70,4 +70,73 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_score_table() {
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = partitionElection::ballots::get_profile(3, "PA", &ctx);
+        let borda = Borda::new(3);
+        let copeland = Copeland::new(3);
+
+        let table = borda.score_table(&profile, None);
+        assert!(std::rc::Rc::ptr_eq(
+            &table,
+            &borda.score_table(&profile, None)
+        ));
+        assert!(!std::rc::Rc::ptr_eq(
+            &table,
+            &copeland.score_table(&profile, None)
+        ));
+        for (partition, score) in table.iter() {
+            assert_eq!(*score, borda.score(partition, &profile, None));
+        }
+        assert!(!std::rc::Rc::ptr_eq(
+            &table,
+            &Borda::new(3).score_table(&profile, None)
+        ));
+
+        // Extra votes are compared by their terms, the least recently used tables are evicted.
+        let extra_votes = profile
+            .votes
+            .keys()
+            .map(|ranking| BTreeMap::from([(ranking.clone(), z3::ast::Int::from_i64(&ctx, 1))]))
+            .collect::<Vec<_>>();
+        let first = borda.score_table(&profile, Some(&extra_votes[0]));
+        let rebuilt = BTreeMap::from([(
+            profile.votes.keys().next().unwrap().clone(),
+            z3::ast::Int::from_i64(&ctx, 1),
+        )]);
+        assert!(std::rc::Rc::ptr_eq(
+            &first,
+            &borda.score_table(&profile, Some(&rebuilt))
+        ));
+        for extra in extra_votes.iter() {
+            borda.score_table(&profile, Some(extra));
+        }
+        assert!(!std::rc::Rc::ptr_eq(
+            &first,
+            &borda.score_table(&profile, Some(&extra_votes[0]))
+        ));
+        assert!(std::rc::Rc::ptr_eq(
+            &table,
+            &borda.score_table(&profile, None)
+        ));
+    }
+
+    #[test]
+    fn test_tie_breaking_never_ties() {
+        // ```tied``` borrows the rule as long as the context.
+        let borda = Borda::new(3);
+        let tie_breaking = TieBreaking::create(3, &borda, None);
+        let ctx = z3::Context::new(&z3::Config::new());
+        let profile = partitionElection::ballots::get_profile(3, "FP", &ctx);
+
+        let partitions = Partition::all(3);
+        for a in &partitions {
+            for b in &partitions {
+                let tied = tie_breaking.tied(a, b, &profile, None);
+                assert_eq!(tied.simplify().as_bool(), Some(a == b));
+            }
+        }
+    }
 }